@@ -1,6 +1,6 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, Balance, BalancesConfig, GenesisConfig, GrandpaConfig,
+	KittiesModuleConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -36,6 +36,19 @@ pub fn authority_keys_from_seed(s: &str) -> (AuraId, GrandpaId) {
 	(get_from_seed::<AuraId>(s), get_from_seed::<GrandpaId>(s))
 }
 
+/// A starting collection of kitties for Alice and Bob, as `(owner, dna, price)`.
+///
+/// Alice gets a male and a female kitty so that breeding can be tried straight away.
+fn demo_kitties() -> Vec<(AccountId, Vec<u8>, Balance)> {
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+	vec![
+		(alice.clone(), b"alice-kitty-01".to_vec(), 1_000_000_000_000),
+		(alice, b"alice-kitty-002".to_vec(), 2_000_000_000_000),
+		(bob, b"bob-kitty-01".to_vec(), 1_500_000_000_000),
+	]
+}
+
 pub fn development_config() -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
 
@@ -59,6 +72,8 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Pre-minted kitties
+				demo_kitties(),
 				true,
 			)
 		},
@@ -107,6 +122,8 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Pre-minted kitties
+				demo_kitties(),
 				true,
 			)
 		},
@@ -130,6 +147,7 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	initial_kitties: Vec<(AccountId, Vec<u8>, Balance)>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -152,5 +170,9 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		kitties_module: KittiesModuleConfig {
+			// Mint the starting kitty collection.
+			kitties: initial_kitties,
+		},
 	}
 }
@@ -0,0 +1,38 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to kitties, so clients do not have to compute raw storage keys.
+	pub trait KittiesApi<AccountId, Balance, Kitty, Genome, Metadata, IntegrityError> where
+		AccountId: Codec,
+		Balance: Codec,
+		Kitty: Codec,
+		Genome: Codec,
+		Metadata: Codec,
+		IntegrityError: Codec,
+	{
+		/// The kitty with the given DNA, if it exists.
+		fn kitty(dna: Vec<u8>) -> Option<Kitty>;
+		/// The traits decoded from the DNA of the kitty, if it exists.
+		fn genome(dna: Vec<u8>) -> Option<Genome>;
+		/// Up to `limit` DNAs of kitties owned by `owner`, starting after `start_after`. Pass the
+		/// last DNA of one page as `start_after` to get the next one.
+		fn kitties_of(owner: AccountId, start_after: Option<Vec<u8>>, limit: u32) -> Vec<Vec<u8>>;
+		/// The total number of kitties.
+		fn kitty_count() -> u32;
+		/// The borrower of the kitty while it is leased, otherwise its owner. `None` if the kitty
+		/// does not exist.
+		fn kitty_user(dna: Vec<u8>) -> Option<AccountId>;
+		/// The name, URI and attributes of the kitty, if it exists.
+		fn metadata(dna: Vec<u8>) -> Option<Metadata>;
+		/// The DNA and price of every kitty listed for sale.
+		fn listed_kitties() -> Vec<(Vec<u8>, Balance)>;
+		/// Every inconsistency between the kitty storage items. Reads all kitties, so it is
+		/// meant for debugging only.
+		fn integrity_errors() -> Vec<IntegrityError>;
+	}
+}
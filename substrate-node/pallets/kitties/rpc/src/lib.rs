@@ -0,0 +1,184 @@
+//! RPC interface for the kitties pallet.
+//!
+//! Exposes the `KittiesApi` runtime API under the `kitties_*` namespace so that frontends can
+//! read kitties without computing raw storage keys. `kitties_integrityErrors` walks all kitty
+//! storage and is only served when unsafe RPC methods are allowed.
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+pub use pallet_kitties_rpc_runtime_api::KittiesApi as KittiesRuntimeApi;
+use sc_rpc_api::DenyUnsafe;
+use serde::{de::DeserializeOwned, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash, AccountId, Balance, Kitty, Genome, Metadata, IntegrityError> {
+	/// The kitty with the given DNA, if it exists.
+	#[method(name = "kitties_kitty")]
+	fn kitty(&self, dna: Bytes, at: Option<BlockHash>) -> RpcResult<Option<Kitty>>;
+
+	/// The traits decoded from the DNA of the kitty, if it exists.
+	#[method(name = "kitties_genome")]
+	fn genome(&self, dna: Bytes, at: Option<BlockHash>) -> RpcResult<Option<Genome>>;
+
+	/// Up to `limit` DNAs of kitties owned by `owner`, starting after the DNA `start_after`.
+	#[method(name = "kitties_kittiesOf")]
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<Bytes>,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<Bytes>>;
+
+	/// The total number of kitties.
+	#[method(name = "kitties_count")]
+	fn kitty_count(&self, at: Option<BlockHash>) -> RpcResult<u32>;
+
+	/// The account that may use the kitty: its borrower while it is leased, otherwise its owner.
+	#[method(name = "kitties_user")]
+	fn kitty_user(&self, dna: Bytes, at: Option<BlockHash>) -> RpcResult<Option<AccountId>>;
+
+	/// The name, URI and attributes of the kitty, if it exists.
+	#[method(name = "kitties_metadata")]
+	fn metadata(&self, dna: Bytes, at: Option<BlockHash>) -> RpcResult<Option<Metadata>>;
+
+	/// The DNA and price of every kitty listed for sale.
+	#[method(name = "kitties_listed")]
+	fn listed_kitties(&self, at: Option<BlockHash>) -> RpcResult<Vec<(Bytes, Balance)>>;
+
+	/// Every inconsistency between the kitty storage items. Unsafe.
+	#[method(name = "kitties_integrityErrors")]
+	fn integrity_errors(&self, at: Option<BlockHash>) -> RpcResult<Vec<IntegrityError>>;
+}
+
+/// Provides RPC methods to query kitties.
+pub struct Kitties<C, Block> {
+	/// Shared reference to the client.
+	client: Arc<C>,
+	/// Whether to deny unsafe calls.
+	deny_unsafe: DenyUnsafe,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Kitties<C, Block> {
+	/// Creates a new instance of the Kitties RPC helper.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe, _marker: Default::default() }
+	}
+}
+
+/// Error code returned when a runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+fn runtime_error(message: &'static str, err: impl std::fmt::Debug) -> CallError {
+	CallError::Custom(ErrorObject::owned(RUNTIME_ERROR, message, Some(format!("{:?}", err))))
+}
+
+#[async_trait]
+impl<C, Block, AccountId, Balance, Kitty, Genome, Metadata, IntegrityError>
+	KittiesApiServer<
+		<Block as BlockT>::Hash,
+		AccountId,
+		Balance,
+		Kitty,
+		Genome,
+		Metadata,
+		IntegrityError,
+	> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, AccountId, Balance, Kitty, Genome, Metadata, IntegrityError>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Balance: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Kitty: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Genome: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	Metadata: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	IntegrityError: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn kitty(&self, dna: Bytes, at: Option<Block::Hash>) -> RpcResult<Option<Kitty>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty(&at, dna.to_vec())
+			.map_err(|e| runtime_error("Unable to query kitty.", e).into())
+	}
+
+	fn genome(&self, dna: Bytes, at: Option<Block::Hash>) -> RpcResult<Option<Genome>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.genome(&at, dna.to_vec())
+			.map_err(|e| runtime_error("Unable to query genome.", e).into())
+	}
+
+	fn kitties_of(
+		&self,
+		owner: AccountId,
+		start_after: Option<Bytes>,
+		limit: u32,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Vec<Bytes>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let kitties = api
+			.kitties_of(&at, owner, start_after.map(|dna| dna.to_vec()), limit)
+			.map_err(|e| runtime_error("Unable to query kitties of owner.", e))?;
+		Ok(kitties.into_iter().map(Into::into).collect())
+	}
+
+	fn kitty_count(&self, at: Option<Block::Hash>) -> RpcResult<u32> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty_count(&at)
+			.map_err(|e| runtime_error("Unable to query kitty count.", e).into())
+	}
+
+	fn kitty_user(&self, dna: Bytes, at: Option<Block::Hash>) -> RpcResult<Option<AccountId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.kitty_user(&at, dna.to_vec())
+			.map_err(|e| runtime_error("Unable to query kitty user.", e).into())
+	}
+
+	fn metadata(&self, dna: Bytes, at: Option<Block::Hash>) -> RpcResult<Option<Metadata>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.metadata(&at, dna.to_vec())
+			.map_err(|e| runtime_error("Unable to query kitty metadata.", e).into())
+	}
+
+	fn listed_kitties(&self, at: Option<Block::Hash>) -> RpcResult<Vec<(Bytes, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let listed = api
+			.listed_kitties(&at)
+			.map_err(|e| runtime_error("Unable to query listed kitties.", e))?;
+		Ok(listed.into_iter().map(|(dna, price)| (dna.into(), price)).collect())
+	}
+
+	fn integrity_errors(&self, at: Option<Block::Hash>) -> RpcResult<Vec<IntegrityError>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.integrity_errors(&at)
+			.map_err(|e| runtime_error("Unable to check kitty integrity.", e).into())
+	}
+}
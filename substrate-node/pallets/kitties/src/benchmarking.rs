@@ -1,20 +1,601 @@
-//! Benchmarking setup for pallet-template
+//! Benchmarking setup for pallet-kitties
 
 use super::*;
 
 #[allow(unused)]
-use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use crate::Pallet as PalletKitties;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
+use sp_runtime::traits::Bounded;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+// Mint a priced kitty with the longest possible DNA for `owner`.
+fn mint_priced_kitty<T: Config>(owner: &T::AccountId) -> Vec<u8> {
+	let dna = vec![0u8; T::MaxDnaLen::get() as usize];
+	let price = T::Currency::minimum_balance() * 10u32.into();
+	mint_royalty_kitty::<T>(owner, dna.clone(), price);
+	dna
+}
+
+// Mint a kitty for `owner` that pays the largest royalty to another account, so that selling it
+// takes the most expensive path.
+fn mint_royalty_kitty<T: Config>(owner: &T::AccountId, dna: Vec<u8>, price: BalanceOf<T>) {
+	let creator = funded_account::<T>("creator", 0);
+	let dna = Pallet::<T>::to_dna(dna).unwrap();
+	Pallet::<T>::mint(&creator, dna.clone(), price, None, 0, T::MaxRoyalty::get(), None).unwrap();
+	let kitty = Kitties::<T>::get(&dna).unwrap();
+	Pallet::<T>::transfer_kitty(&dna, kitty, owner).unwrap();
+}
+
+// Give a kitty of `owner` the longest name and URI, and `a` attributes with the longest keys and
+// values, so that burning it releases every kind of metadata deposit.
+fn add_metadata<T: Config>(owner: &T::AccountId, dna: &[u8], a: u32) -> DispatchResult {
+	let origin: T::Origin = RawOrigin::Signed(owner.clone()).into();
+	let name = vec![0u8; T::MaxNameLen::get() as usize];
+	Pallet::<T>::set_kitty_name(origin.clone(), dna.to_vec(), name)?;
+	let uri = vec![0u8; T::MaxUriLen::get() as usize];
+	Pallet::<T>::set_metadata(origin.clone(), dna.to_vec(), uri)?;
+	for i in 0..a {
+		let key = vec![i as u8; T::MaxAttributeKeyLen::get() as usize];
+		let value = vec![0u8; T::MaxAttributeValueLen::get() as usize];
+		Pallet::<T>::set_attribute(origin.clone(), dna.to_vec(), key, value)?;
+	}
+	Ok(())
+}
+
+// Mint a kitty with the longest possible DNA for `owner` into a new collection owned by `owner`,
+// so that checks on the kitty's collection are not skipped.
+fn mint_collection_kitty<T: Config>(owner: &T::AccountId) -> Result<Vec<u8>, DispatchError> {
+	let origin: T::Origin = RawOrigin::Signed(owner.clone()).into();
+	let collection = NextCollectionId::<T>::get();
+	Pallet::<T>::create_collection(origin.clone(), None, Zero::zero(), MintPolicy::OwnerOnly)?;
+	let dna = vec![0u8; T::MaxDnaLen::get() as usize];
+	let salt = commit_dna::<T>(owner, &dna)?;
+	Pallet::<T>::mint_into_collection(origin, collection, dna.clone(), salt, Permill::zero())?;
+	Ok(dna)
+}
+
+// Commit `who` to `dna` and move to the next block, so that the commitment can be revealed.
+fn commit_dna<T: Config>(who: &T::AccountId, dna: &[u8]) -> Result<[u8; 32], DispatchError> {
+	let salt = [0u8; 32];
+	let hash = T::Hashing::hash_of(&(who, dna, &salt));
+	Pallet::<T>::commit_kitty(RawOrigin::Signed(who.clone()).into(), hash)?;
+	let next = frame_system::Pallet::<T>::block_number() + 1u32.into();
+	frame_system::Pallet::<T>::set_block_number(next);
+	Ok(salt)
+}
+
+fn funded_account<T: Config>(name: &'static str, index: u32) -> T::AccountId {
+	let who: T::AccountId = account(name, index, SEED);
+	T::Currency::make_free_balance_be(&who, BalanceOf::<T>::max_value() / 2u32.into());
+	who
+}
 
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	create_kitty {
+		let d in 1 .. T::MaxDnaLen::get();
+		let caller = funded_account::<T>("caller", 0);
+		let dna = vec![0u8; d as usize];
+		let salt = commit_dna::<T>(&caller, &dna)?;
+	}: _(RawOrigin::Signed(caller.clone()), dna.clone(), salt, 100u32.into(), T::MaxRoyalty::get())
+	verify {
+		assert!(Kitties::<T>::get(Pallet::<T>::to_dna(dna).unwrap()).is_some());
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 1);
+	}
+
+	change_kitty_owner {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		let new_owner: T::AccountId = account("new_owner", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), dna.clone(), new_owner.clone())
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&new_owner), 1);
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 0);
+	}
+
+	set_price {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), dna, 1_000u32.into())
+
+	list_for_sale {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(KittiesForSale::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	delist {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		Pallet::<T>::list_for_sale(RawOrigin::Signed(caller.clone()).into(), dna.clone())?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(!KittiesForSale::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	buy_kitty {
+		let seller = funded_account::<T>("seller", 0);
+		let dna = mint_priced_kitty::<T>(&seller);
+		Pallet::<T>::list_for_sale(RawOrigin::Signed(seller.clone()).into(), dna.clone())?;
+		let buyer = funded_account::<T>("buyer", 0);
+	}: _(RawOrigin::Signed(buyer.clone()), dna, BalanceOf::<T>::max_value())
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&buyer), 1);
+	}
+
+	// An operator of the parents' owner takes the most expensive path.
+	// Mixing the parents' DNA takes time linear in its length.
+	breed_kitty {
+		let d in 1 .. T::MaxDnaLen::get();
+		let caller = funded_account::<T>("caller", 0);
+		let owner: T::AccountId = account("owner", 0, SEED);
+		// The default decoder reads gender from the last byte, so these are a female and a male.
+		let parent_a = vec![1u8; d as usize];
+		let parent_b = vec![2u8; d as usize];
+		Pallet::<T>::mint(&owner, Pallet::<T>::to_dna(parent_a.clone()).unwrap(), 1u32.into(), None, 0, Permill::zero(), None)?;
+		Pallet::<T>::mint(&owner, Pallet::<T>::to_dna(parent_b.clone()).unwrap(), 1u32.into(), None, 0, Permill::zero(), None)?;
+		Pallet::<T>::set_approval_for_all(RawOrigin::Signed(owner.clone()).into(), caller.clone(), true)?;
+	}: _(RawOrigin::Signed(caller.clone()), parent_a, parent_b)
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&owner), 2);
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 1);
+	}
+
+	mint_random_kitty {
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller.clone()), T::MaxRoyalty::get())
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 1);
+	}
+
+	burn_kitty {
+		let a in 0 .. T::MaxAttributes::get();
+		let caller = funded_account::<T>("caller", 0);
+		let dna = vec![0u8; T::MaxDnaLen::get() as usize];
+		let salt = commit_dna::<T>(&caller, &dna)?;
+		Pallet::<T>::create_kitty(RawOrigin::Signed(caller.clone()).into(), dna.clone(), salt, 1u32.into(), Permill::zero())?;
+		add_metadata::<T>(&caller, &dna, a)?;
+	}: _(RawOrigin::Signed(caller.clone()), dna.clone())
+	verify {
+		assert!(!Kitties::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 0);
+	}
+
+	force_burn {
+		let a in 0 .. T::MaxAttributes::get();
+		let owner = funded_account::<T>("owner", 0);
+		let dna = vec![0u8; T::MaxDnaLen::get() as usize];
+		let salt = commit_dna::<T>(&owner, &dna)?;
+		Pallet::<T>::create_kitty(RawOrigin::Signed(owner.clone()).into(), dna.clone(), salt, 1u32.into(), Permill::zero())?;
+		add_metadata::<T>(&owner, &dna, a)?;
+		// A running auction with a bid is the most expensive kitty to remove.
+		Pallet::<T>::create_auction(RawOrigin::Signed(owner).into(), dna.clone(), 1u32.into(), 10u32.into())?;
+		let bidder = funded_account::<T>("bidder", 0);
+		Pallet::<T>::bid(RawOrigin::Signed(bidder).into(), dna.clone(), 10u32.into())?;
+	}: _(RawOrigin::Root, dna.clone())
+	verify {
+		let dna = Pallet::<T>::to_dna(dna).unwrap();
+		assert!(!Kitties::<T>::contains_key(&dna));
+		assert!(!Auctions::<T>::contains_key(&dna));
+	}
+
+	approve {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller), dna.clone(), operator.clone())
+	verify {
+		assert_eq!(KittyApprovals::<T>::get(Pallet::<T>::to_dna(dna).unwrap()), Some(operator));
+	}
+
+	set_approval_for_all {
+		let caller: T::AccountId = whitelisted_caller();
+		let operator: T::AccountId = account("operator", 0, SEED);
+	}: _(RawOrigin::Signed(caller.clone()), operator.clone(), true)
+	verify {
+		assert!(OperatorApprovals::<T>::contains_key(&caller, &operator));
+	}
+
+	transfer_from {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::approve(RawOrigin::Signed(owner.clone()).into(), dna.clone(), caller.clone())?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(caller), dna, to.clone())
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&to), 1);
+		assert_eq!(KittiesOwnedCount::<T>::get(&owner), 0);
+	}
+
+	create_auction {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		Pallet::<T>::list_for_sale(RawOrigin::Signed(caller.clone()).into(), dna.clone())?;
+	}: _(RawOrigin::Signed(caller), dna.clone(), 1u32.into(), 10u32.into())
+	verify {
+		assert!(Auctions::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	bid {
+		let seller = funded_account::<T>("seller", 0);
+		let dna = mint_priced_kitty::<T>(&seller);
+		Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), dna.clone(), 1u32.into(), 10u32.into())?;
+		// Outbid an earlier bidder so that the refund is part of the measurement.
+		let previous = funded_account::<T>("bidder", 0);
+		Pallet::<T>::bid(RawOrigin::Signed(previous).into(), dna.clone(), 10u32.into())?;
+		let bidder = funded_account::<T>("bidder", 1);
+	}: _(RawOrigin::Signed(bidder.clone()), dna.clone(), 20u32.into())
+	verify {
+		let auction = Auctions::<T>::get(Pallet::<T>::to_dna(dna).unwrap()).unwrap();
+		assert_eq!(auction.best_bid, Some((bidder, 20u32.into())));
+	}
+
+	commit_kitty {
+		let caller = funded_account::<T>("caller", 0);
+		let hash = T::Hashing::hash_of(&(&caller, vec![0u8; 32], [0u8; 32]));
+	}: _(RawOrigin::Signed(caller.clone()), hash)
+	verify {
+		assert!(Commitments::<T>::contains_key(&caller, &hash));
+	}
+
+	reveal_kitty {
+		let d in 1 .. T::MaxDnaLen::get();
+		let caller = funded_account::<T>("caller", 0);
+		let dna = vec![0u8; d as usize];
+		let salt = [0u8; 32];
+		let hash = T::Hashing::hash_of(&(&caller, &dna, &salt));
+		Pallet::<T>::commit_kitty(RawOrigin::Signed(caller.clone()).into(), hash)?;
+		let next = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		frame_system::Pallet::<T>::set_block_number(next);
+	}: _(RawOrigin::Signed(caller.clone()), dna, salt, T::MaxRoyalty::get())
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 1);
+		assert!(!Commitments::<T>::contains_key(&caller, &hash));
+	}
+
+	offer_transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		Pallet::<T>::list_for_sale(RawOrigin::Signed(caller.clone()).into(), dna.clone())?;
+		let to: T::AccountId = account("to", 0, SEED);
+	}: _(RawOrigin::Signed(caller), dna.clone(), to.clone())
+	verify {
+		assert_eq!(PendingTransfers::<T>::get(Pallet::<T>::to_dna(dna).unwrap()), Some(to));
+	}
+
+	accept_transfer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::offer_transfer(RawOrigin::Signed(owner.clone()).into(), dna.clone(), caller.clone())?;
+	}: _(RawOrigin::Signed(caller.clone()), dna)
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 1);
+		assert_eq!(KittiesOwnedCount::<T>::get(&owner), 0);
+	}
+
+	reject_transfer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::offer_transfer(RawOrigin::Signed(owner).into(), dna.clone(), caller.clone())?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	cancel_transfer {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		let to: T::AccountId = account("to", 0, SEED);
+		Pallet::<T>::offer_transfer(RawOrigin::Signed(caller.clone()).into(), dna.clone(), to)?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(!PendingTransfers::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	make_offer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let caller = funded_account::<T>("caller", 0);
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller.clone()), dna.clone(), 10u32.into(), expiry)
+	verify {
+		assert!(Offers::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap(), &caller));
+	}
+
+	accept_offer {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		let bidder = funded_account::<T>("bidder", 0);
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Pallet::<T>::make_offer(RawOrigin::Signed(bidder.clone()).into(), dna.clone(), 10u32.into(), expiry)?;
+	}: _(RawOrigin::Signed(caller.clone()), dna, bidder.clone())
+	verify {
+		assert_eq!(KittiesOwnedCount::<T>::get(&bidder), 1);
+		assert_eq!(KittiesOwnedCount::<T>::get(&caller), 0);
+	}
+
+	withdraw_offer {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let caller = funded_account::<T>("caller", 0);
+		let expiry = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Pallet::<T>::make_offer(RawOrigin::Signed(caller.clone()).into(), dna.clone(), 10u32.into(), expiry)?;
+	}: _(RawOrigin::Signed(caller.clone()), dna.clone())
+	verify {
+		assert!(!Offers::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap(), &caller));
+	}
+
+	lend_kitty {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		let borrower: T::AccountId = account("borrower", 0, SEED);
+		let until = frame_system::Pallet::<T>::block_number() + 10u32.into();
+	}: _(RawOrigin::Signed(caller), dna.clone(), borrower, until, 10u32.into())
+	verify {
+		assert!(LeaseOffers::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	accept_lease {
+		let owner = funded_account::<T>("owner", 0);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let caller = funded_account::<T>("caller", 0);
+		let until = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Pallet::<T>::lend_kitty(RawOrigin::Signed(owner).into(), dna.clone(), caller.clone(), until, 10u32.into())?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(Leases::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	cancel_lease {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		let borrower: T::AccountId = account("borrower", 0, SEED);
+		let until = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		Pallet::<T>::lend_kitty(RawOrigin::Signed(caller.clone()).into(), dna.clone(), borrower, until, 10u32.into())?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(!LeaseOffers::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	set_kitty_name {
+		let caller = funded_account::<T>("caller", 0);
+		let dna = mint_collection_kitty::<T>(&caller)?;
+		let name = vec![0u8; T::MaxNameLen::get() as usize];
+	}: _(RawOrigin::Signed(caller), dna.clone(), name)
+	verify {
+		assert!(Metadata::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	set_metadata {
+		let caller = funded_account::<T>("caller", 0);
+		let dna = mint_collection_kitty::<T>(&caller)?;
+		let uri = vec![0u8; T::MaxUriLen::get() as usize];
+	}: _(RawOrigin::Signed(caller), dna.clone(), uri)
+	verify {
+		assert!(Metadata::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	clear_metadata {
+		let caller = funded_account::<T>("caller", 0);
+		let dna = mint_collection_kitty::<T>(&caller)?;
+		add_metadata::<T>(&caller, &dna, 0)?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(!Metadata::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	set_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		let dna = mint_collection_kitty::<T>(&caller)?;
+		let key = vec![0u8; T::MaxAttributeKeyLen::get() as usize];
+		let value = vec![0u8; T::MaxAttributeValueLen::get() as usize];
+	}: _(RawOrigin::Signed(caller), dna.clone(), key, value)
+	verify {
+		assert_eq!(AttributeCount::<T>::get(Pallet::<T>::to_dna(dna).unwrap()), 1);
+	}
+
+	clear_attribute {
+		let caller = funded_account::<T>("caller", 0);
+		let dna = mint_collection_kitty::<T>(&caller)?;
+		add_metadata::<T>(&caller, &dna, 1)?;
+		let key = vec![0u8; T::MaxAttributeKeyLen::get() as usize];
+	}: _(RawOrigin::Signed(caller), dna.clone(), key)
+	verify {
+		assert_eq!(AttributeCount::<T>::get(Pallet::<T>::to_dna(dna).unwrap()), 0);
+	}
+
+	create_collection {
+		let caller = funded_account::<T>("caller", 0);
+	}: _(RawOrigin::Signed(caller.clone()), Some(100), 10u32.into(), MintPolicy::Public)
+	verify {
+		assert!(Collections::<T>::contains_key(0));
+		assert_eq!(CollectionDeposits::<T>::get(0), Some((caller, T::CollectionDeposit::get())));
+	}
+
+	destroy_collection {
+		let caller = funded_account::<T>("caller", 0);
+		Pallet::<T>::create_collection(RawOrigin::Signed(caller.clone()).into(), None, Zero::zero(), MintPolicy::OwnerOnly)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(!Collections::<T>::contains_key(0));
+		assert!(!CollectionDeposits::<T>::contains_key(0));
+	}
+
+	set_collection_mint {
+		let owner = funded_account::<T>("owner", 0);
+		Pallet::<T>::create_collection(RawOrigin::Signed(owner.clone()).into(), None, Zero::zero(), MintPolicy::OwnerOnly)?;
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		Pallet::<T>::set_collection_admin(RawOrigin::Signed(owner).into(), 0, caller.clone(), true)?;
+	}: _(RawOrigin::Signed(caller), 0, Some(100), 10u32.into(), MintPolicy::Public)
+	verify {
+		assert_eq!(Collections::<T>::get(0).unwrap().mint_policy, MintPolicy::Public);
+	}
+
+	set_collection_admin {
+		let caller = funded_account::<T>("caller", 0);
+		Pallet::<T>::create_collection(RawOrigin::Signed(caller.clone()).into(), None, Zero::zero(), MintPolicy::OwnerOnly)?;
+		let admin: T::AccountId = account("admin", 0, SEED);
+	}: _(RawOrigin::Signed(caller), 0, admin.clone(), true)
+	verify {
+		assert!(CollectionAdmins::<T>::contains_key(0, admin));
+	}
+
+	set_allow_list {
+		let owner = funded_account::<T>("owner", 0);
+		Pallet::<T>::create_collection(RawOrigin::Signed(owner.clone()).into(), None, Zero::zero(), MintPolicy::AllowList)?;
+		let caller: T::AccountId = whitelisted_caller();
+		Pallet::<T>::set_collection_admin(RawOrigin::Signed(owner).into(), 0, caller.clone(), true)?;
+		let minter: T::AccountId = account("minter", 0, SEED);
+	}: _(RawOrigin::Signed(caller), 0, minter.clone(), true)
+	verify {
+		assert!(CollectionAllowList::<T>::contains_key(0, minter));
+	}
+
+	freeze_collection_metadata {
+		let caller = funded_account::<T>("caller", 0);
+		Pallet::<T>::create_collection(RawOrigin::Signed(caller.clone()).into(), None, Zero::zero(), MintPolicy::OwnerOnly)?;
+	}: _(RawOrigin::Signed(caller), 0)
+	verify {
+		assert!(Collections::<T>::get(0).unwrap().metadata_frozen);
+	}
+
+	// An allow-listed minter that pays the mint price takes the most expensive path.
+	mint_into_collection {
+		let d in 1 .. T::MaxDnaLen::get();
+		let owner = funded_account::<T>("owner", 0);
+		let price = T::Currency::minimum_balance() * 10u32.into();
+		Pallet::<T>::create_collection(RawOrigin::Signed(owner.clone()).into(), Some(1), price, MintPolicy::AllowList)?;
+		let caller = funded_account::<T>("caller", 0);
+		Pallet::<T>::set_allow_list(RawOrigin::Signed(owner).into(), 0, caller.clone(), true)?;
+		let dna = vec![0u8; d as usize];
+		let salt = commit_dna::<T>(&caller, &dna)?;
+	}: _(RawOrigin::Signed(caller), 0, dna.clone(), salt, T::MaxRoyalty::get())
+	verify {
+		assert_eq!(Kitties::<T>::get(Pallet::<T>::to_dna(dna).unwrap()).unwrap().collection, Some(0));
+	}
+
+	lock_kitty {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		Pallet::<T>::list_for_sale(RawOrigin::Signed(caller.clone()).into(), dna.clone())?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(LockedKitties::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	unlock_kitty {
+		let caller: T::AccountId = whitelisted_caller();
+		let dna = mint_priced_kitty::<T>(&caller);
+		Pallet::<T>::lock_kitty(RawOrigin::Signed(caller.clone()).into(), dna.clone())?;
+	}: _(RawOrigin::Signed(caller), dna.clone())
+	verify {
+		assert!(!LockedKitties::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	// Freezing a kitty in auction cancels the auction and refunds its best bid.
+	freeze_kitty {
+		let seller = funded_account::<T>("seller", 0);
+		let dna = mint_priced_kitty::<T>(&seller);
+		Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), dna.clone(), 1u32.into(), 10u32.into())?;
+		let bidder = funded_account::<T>("bidder", 0);
+		Pallet::<T>::bid(RawOrigin::Signed(bidder).into(), dna.clone(), 10u32.into())?;
+	}: _(RawOrigin::Root, dna.clone())
+	verify {
+		let dna = Pallet::<T>::to_dna(dna).unwrap();
+		assert!(FrozenKitties::<T>::contains_key(&dna));
+		assert!(!Auctions::<T>::contains_key(&dna));
+	}
+
+	thaw_kitty {
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		Pallet::<T>::freeze_kitty(RawOrigin::Root.into(), dna.clone())?;
+	}: _(RawOrigin::Root, dna.clone())
+	verify {
+		assert!(!FrozenKitties::<T>::contains_key(Pallet::<T>::to_dna(dna).unwrap()));
+	}
+
+	on_initialize {
+		let n in 0 .. T::MaxAuctionsPerBlock::get();
+		let end = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		let mut bidders = Vec::new();
+		for i in 0..n {
+			let seller = funded_account::<T>("seller", i);
+			let mut dna = b"a".to_vec();
+			dna.extend_from_slice(&i.to_le_bytes());
+			mint_royalty_kitty::<T>(&seller, dna.clone(), 1u32.into());
+			Pallet::<T>::create_auction(RawOrigin::Signed(seller).into(), dna.clone(), 1u32.into(), 1u32.into())?;
+			let bidder = funded_account::<T>("bidder", i);
+			Pallet::<T>::bid(RawOrigin::Signed(bidder.clone()).into(), dna, 10u32.into())?;
+			bidders.push(bidder);
+		}
+	}: {
+		PalletKitties::<T>::on_initialize(end);
+	}
+	verify {
+		assert_eq!(Auctions::<T>::iter().count(), 0);
+		for bidder in bidders {
+			assert_eq!(KittiesOwnedCount::<T>::get(&bidder), 1);
+		}
+	}
+
+	expire_commitments {
+		let n in 0 .. T::MaxCommitsPerBlock::get();
+		let expiry = frame_system::Pallet::<T>::block_number() + T::CommitPeriod::get();
+		for i in 0..n {
+			let committer = funded_account::<T>("committer", i);
+			let hash = T::Hashing::hash_of(&i);
+			Pallet::<T>::commit_kitty(RawOrigin::Signed(committer).into(), hash)?;
+		}
+	}: {
+		PalletKitties::<T>::on_initialize(expiry);
+	}
+	verify {
+		assert_eq!(Commitments::<T>::iter().count(), 0);
+	}
+
+	expire_offers {
+		let n in 0 .. T::MaxOffersPerBlock::get();
+		let owner: T::AccountId = account("owner", 0, SEED);
+		let dna = mint_priced_kitty::<T>(&owner);
+		let expiry = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		for i in 0..n {
+			let bidder = funded_account::<T>("bidder", i);
+			Pallet::<T>::make_offer(RawOrigin::Signed(bidder).into(), dna.clone(), 10u32.into(), expiry)?;
+		}
+		OfferExpiryCursor::<T>::put(expiry);
+	}: {
+		PalletKitties::<T>::on_idle(expiry, Weight::max_value());
+	}
+	verify {
+		assert_eq!(Offers::<T>::iter().count(), 0);
+	}
+
+	expire_leases {
+		let n in 0 .. T::MaxLeasesPerBlock::get();
+		let until = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		for i in 0..n {
+			let owner = funded_account::<T>("owner", i);
+			let mut dna = b"l".to_vec();
+			dna.extend_from_slice(&i.to_le_bytes());
+			Pallet::<T>::mint(&owner, Pallet::<T>::to_dna(dna.clone()).unwrap(), 1u32.into(), None, 0, Permill::zero(), None)?;
+			let borrower = funded_account::<T>("borrower", i);
+			Pallet::<T>::lend_kitty(RawOrigin::Signed(owner).into(), dna.clone(), borrower.clone(), until, 10u32.into())?;
+			Pallet::<T>::accept_lease(RawOrigin::Signed(borrower).into(), dna)?;
+		}
+	}: {
+		PalletKitties::<T>::on_initialize(until);
+	}
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert_eq!(Leases::<T>::iter().count(), 0);
 	}
 
-	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
+	impl_benchmark_test_suite!(PalletKitties, crate::mock::new_bench_ext(), crate::mock::Test);
 }
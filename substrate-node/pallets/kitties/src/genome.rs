@@ -0,0 +1,111 @@
+//! Decoding of kitty DNA into typed traits.
+//!
+//! The runtime picks how DNA is read through `Config::GenomeDecoder`. `ByteGenomeDecoder` is a
+//! simple default that reads each trait from a fixed byte of the DNA.
+
+use super::*;
+
+// Fur colour of a kitty.
+#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum FurColour {
+	Black,
+	White,
+	Ginger,
+	Grey,
+	Cream,
+	Chocolate,
+}
+
+// Eye colour of a kitty.
+#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EyeColour {
+	Green,
+	Blue,
+	Amber,
+	Copper,
+}
+
+// Coat pattern of a kitty.
+#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Pattern {
+	Solid,
+	Tabby,
+	Spotted,
+	Tortoiseshell,
+	Calico,
+}
+
+// Rarity tier of a kitty, from most to least common.
+#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum Rarity {
+	Common,
+	Uncommon,
+	Rare,
+	Epic,
+	Legendary,
+}
+
+// The traits encoded in a kitty's DNA.
+#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Genome {
+	pub gender: Gender,
+	pub fur: FurColour,
+	pub eyes: EyeColour,
+	pub pattern: Pattern,
+	pub rarity: Rarity,
+}
+
+/// Turns DNA into a `Genome`.
+pub trait GenomeDecoder {
+	/// Decode `dna`. Every DNA, including the empty one, must decode to some genome.
+	fn decode(dna: &[u8]) -> Genome;
+}
+
+/// Reads fur, eyes, pattern and rarity from bytes 0 to 3 and gender from the parity of the last
+/// byte. Missing bytes read as zero.
+pub struct ByteGenomeDecoder;
+
+impl GenomeDecoder for ByteGenomeDecoder {
+	fn decode(dna: &[u8]) -> Genome {
+		let byte = |i: usize| dna.get(i).copied().unwrap_or_default();
+		let last = dna.last().copied().unwrap_or_default();
+
+		let gender = if last % 2 == 0 { Gender::Male } else { Gender::Female };
+		let fur = match byte(0) % 6 {
+			0 => FurColour::Black,
+			1 => FurColour::White,
+			2 => FurColour::Ginger,
+			3 => FurColour::Grey,
+			4 => FurColour::Cream,
+			_ => FurColour::Chocolate,
+		};
+		let eyes = match byte(1) % 4 {
+			0 => EyeColour::Green,
+			1 => EyeColour::Blue,
+			2 => EyeColour::Amber,
+			_ => EyeColour::Copper,
+		};
+		let pattern = match byte(2) % 5 {
+			0 => Pattern::Solid,
+			1 => Pattern::Tabby,
+			2 => Pattern::Spotted,
+			3 => Pattern::Tortoiseshell,
+			_ => Pattern::Calico,
+		};
+		// Each tier is roughly half as likely as the one below it.
+		let rarity = match byte(3) {
+			0..=127 => Rarity::Common,
+			128..=191 => Rarity::Uncommon,
+			192..=231 => Rarity::Rare,
+			232..=251 => Rarity::Epic,
+			_ => Rarity::Legendary,
+		};
+
+		Genome { gender, fur, eyes, pattern, rarity }
+	}
+}
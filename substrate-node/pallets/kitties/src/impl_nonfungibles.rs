@@ -0,0 +1,65 @@
+//! Implementations for the `nonfungible` traits, so that other pallets can treat kitties as
+//! generic NFTs.
+//!
+//! Items are identified by their DNA.
+
+use super::*;
+use frame_support::traits::tokens::nonfungible::{Inspect, Mutate, Transfer};
+
+impl<T: Config> Inspect<T::AccountId> for Pallet<T> {
+	type ItemId = Vec<u8>;
+
+	fn owner(item: &Self::ItemId) -> Option<T::AccountId> {
+		Self::kitty_by_dna(item.clone()).map(|kitty| kitty.owner)
+	}
+
+	// Attributes are the ones set with `set_attribute`.
+	fn attribute(item: &Self::ItemId, key: &[u8]) -> Option<Vec<u8>> {
+		let dna = Self::to_dna(item.clone()).ok()?;
+		let key = AttributeKey::<T>::try_from(key.to_vec()).ok()?;
+		<Attributes<T>>::get(&dna, &key).map(|attribute| attribute.value.into_inner())
+	}
+
+	// A kitty cannot change owner while it is being auctioned or offered to another account.
+	fn can_transfer(item: &Self::ItemId) -> bool {
+		match Self::to_dna(item.clone()) {
+			Ok(dna) => <Kitties<T>>::contains_key(&dna) && Self::ensure_transferable(&dna).is_ok(),
+			Err(_) => false,
+		}
+	}
+}
+
+impl<T: Config> Transfer<T::AccountId> for Pallet<T> {
+	fn transfer(item: &Self::ItemId, destination: &T::AccountId) -> DispatchResult {
+		let dna = Self::to_dna(item.clone())?;
+		let kitty = <Kitties<T>>::get(&dna).ok_or(Error::<T>::KittyNotExist)?;
+		Self::ensure_transferable(&dna)?;
+
+		let owner = kitty.owner.clone();
+		Self::transfer_kitty(&dna, kitty, destination)?;
+
+		Self::deposit_event(Event::KittyChangedOwner(item.clone(), owner, destination.clone()));
+		Ok(())
+	}
+}
+
+impl<T: Config> Mutate<T::AccountId> for Pallet<T> {
+	// Mints an unpriced kitty. No creation deposit is taken, as the caller is another pallet.
+	fn mint_into(item: &Self::ItemId, who: &T::AccountId) -> DispatchResult {
+		Self::mint(who, Self::to_dna(item.clone())?, Zero::zero(), None, 0, Permill::zero(), None)?;
+
+		Self::deposit_event(Event::KittyStored(item.clone(), Zero::zero()));
+		Ok(())
+	}
+
+	fn burn(item: &Self::ItemId, maybe_check_owner: Option<&T::AccountId>) -> DispatchResult {
+		let dna = Self::to_dna(item.clone())?;
+		let kitty = <Kitties<T>>::get(&dna).ok_or(Error::<T>::KittyNotExist)?;
+		if let Some(check_owner) = maybe_check_owner {
+			ensure!(kitty.owner == *check_owner, Error::<T>::KittyNotOwned);
+		}
+		Self::ensure_transferable(&dna)?;
+
+		Pallet::<T>::burn(&dna, kitty)
+	}
+}
@@ -0,0 +1,95 @@
+//! Consistency checks between `NumOfKitties`, `Kitties`, `KittiesOwned` and `KittiesOwnedCount`.
+//!
+//! `Pallet::integrity_errors` walks all four storage items and reports every inconsistency
+//! instead of stopping at the first. It is run by the tests, by try-runtime after an upgrade and
+//! by the `kitties_integrityErrors` debug RPC.
+
+use super::*;
+use sp_std::collections::btree_map::BTreeMap;
+
+/// One inconsistency between the kitty storage items.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum IntegrityError<AccountId> {
+	/// `NumOfKitties` differs from the number of entries in `Kitties`.
+	CountMismatch { recorded: u32, actual: u32 },
+	/// The owner index of `listed_under` contains a DNA with no kitty.
+	MissingKitty { listed_under: AccountId, dna: Vec<u8> },
+	/// The owner index of `listed_under` contains a kitty owned by `owner`.
+	WrongOwner { listed_under: AccountId, dna: Vec<u8>, owner: AccountId },
+	/// The DNA appears more than once in the owner index, under one or several owners.
+	DuplicateDna { dna: Vec<u8>, listed_under: Vec<AccountId> },
+	/// The kitty is missing from the owner index of its owner.
+	NotIndexed { dna: Vec<u8>, owner: AccountId },
+	/// `KittiesOwnedCount` of `owner` differs from the number of entries in its owner index.
+	OwnerCountMismatch { owner: AccountId, recorded: u32, actual: u32 },
+}
+
+impl<T: Config> Pallet<T> {
+	/// Every inconsistency between `NumOfKitties`, `Kitties`, `KittiesOwned` and
+	/// `KittiesOwnedCount`. Empty when the storage is consistent.
+	///
+	/// This reads the whole of all four items, so it must not be called from a dispatchable.
+	pub fn integrity_errors() -> Vec<IntegrityError<T::AccountId>> {
+		let mut errors = Vec::new();
+
+		let recorded = <NumOfKitties<T>>::get();
+		let actual = <Kitties<T>>::iter_keys().count() as u32;
+		if recorded != actual {
+			errors.push(IntegrityError::CountMismatch { recorded, actual });
+		}
+
+		// Where each DNA is listed in the owner index, and how many entries each owner has.
+		let mut listings: BTreeMap<Vec<u8>, Vec<T::AccountId>> = BTreeMap::new();
+		let mut owner_counts: BTreeMap<T::AccountId, u32> = BTreeMap::new();
+		for (listed_under, dna, ()) in <KittiesOwned<T>>::iter() {
+			match <Kitties<T>>::get(&dna) {
+				None => errors.push(IntegrityError::MissingKitty {
+					listed_under: listed_under.clone(),
+					dna: dna.to_vec(),
+				}),
+				Some(kitty) if kitty.owner != listed_under =>
+					errors.push(IntegrityError::WrongOwner {
+						listed_under: listed_under.clone(),
+						dna: dna.to_vec(),
+						owner: kitty.owner,
+					}),
+				Some(_) => (),
+			}
+			*owner_counts.entry(listed_under.clone()).or_default() += 1;
+			listings.entry(dna.into_inner()).or_default().push(listed_under);
+		}
+
+		for (owner, recorded) in <KittiesOwnedCount<T>>::iter() {
+			let actual = owner_counts.remove(&owner).unwrap_or_default();
+			if recorded != actual {
+				errors.push(IntegrityError::OwnerCountMismatch { owner, recorded, actual });
+			}
+		}
+		// Owners with index entries but no recorded count.
+		for (owner, actual) in owner_counts {
+			errors.push(IntegrityError::OwnerCountMismatch { owner, recorded: 0, actual });
+		}
+
+		for (dna, listed_under) in listings.iter() {
+			if listed_under.len() > 1 {
+				errors.push(IntegrityError::DuplicateDna {
+					dna: dna.clone(),
+					listed_under: listed_under.clone(),
+				});
+			}
+		}
+
+		for (dna, kitty) in <Kitties<T>>::iter() {
+			let indexed = listings
+				.get(dna.as_slice())
+				.map_or(false, |listed_under| listed_under.contains(&kitty.owner));
+			if !indexed {
+				errors.push(IntegrityError::NotIndexed { dna: dna.into_inner(), owner: kitty.owner });
+			}
+		}
+
+		errors
+	}
+}
@@ -5,26 +5,85 @@
 /// <https://docs.substrate.io/v3/runtime/frame>
 pub use pallet::*;
 
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+mod impl_nonfungibles;
+
+pub mod genome;
+pub use genome::{ByteGenomeDecoder, Genome, GenomeDecoder};
+
+pub mod migrations;
+
+mod integrity;
+pub use integrity::IntegrityError;
+
 use frame_support::pallet_prelude::*;
 use frame_system::pallet_prelude::*;
 use frame_support::inherent::Vec;
+use frame_support::traits::{
+	BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency,
+};
+use frame_support::transactional;
+use sp_runtime::{
+	traits::{Hash, One, Saturating, Zero},
+	Permill,
+};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub type BalanceOf<T> =
+	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+pub type Dna<T> = BoundedVec<u8, <T as Config>::MaxDnaLen>;
+pub type KittyName<T> = BoundedVec<u8, <T as Config>::MaxNameLen>;
+pub type KittyUri<T> = BoundedVec<u8, <T as Config>::MaxUriLen>;
+pub type AttributeKey<T> = BoundedVec<u8, <T as Config>::MaxAttributeKeyLen>;
+pub type AttributeValue<T> = BoundedVec<u8, <T as Config>::MaxAttributeValueLen>;
+pub type CollectionId = u32;
 
 #[frame_support::pallet]
 pub mod pallet {
 	pub use super::*;
 
 	// Kitty struct is used to store the kitty's data.
-	#[derive(TypeInfo, Default, Encode, Decode)]
+	#[derive(TypeInfo, Default, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	#[cfg_attr(feature = "std", serde(bound = ""))]
 	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
 	pub struct Kitty<T:Config> {
-		dna: Vec<u8>,
-		owner: T::AccountId,
-		price: u32,
-		gender: Gender,
+		pub dna: Dna<T>,
+		pub owner: T::AccountId,
+		pub price: BalanceOf<T>,
+		pub gender: Gender,
+		pub parents: Option<(Dna<T>, Dna<T>)>,
+		pub generation: u32,
+		/// The account that minted the kitty.
+		pub creator: T::AccountId,
+		/// The share of every later sale price paid to `creator`.
+		pub royalty: Permill,
+		/// The collection the kitty was minted into, if any.
+		pub collection: Option<CollectionId>,
+		/// The block the kitty was minted or bred in.
+		pub birth_block: T::BlockNumber,
+		/// The first block in which the kitty can breed again.
+		pub cooldown_until: T::BlockNumber,
+		/// Tells the kitty apart from earlier kitties with the same DNA that have been burned.
+		pub mint_nonce: u64,
 	}
 
 	// Enum Gender
-	#[derive(TypeInfo, Encode, Decode, Debug)]
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 	pub enum Gender {
 		Male,
 		Female,
@@ -36,16 +95,238 @@ pub mod pallet {
 		}
 	}
 
+	// Enum MintPolicy decides who may mint kitties into a collection.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Debug, Clone, Copy, PartialEq, Eq)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum MintPolicy {
+		/// Only the collection owner and its admins.
+		OwnerOnly,
+		/// The owner, its admins and the accounts on the collection's allow-list.
+		AllowList,
+		/// Anyone.
+		Public,
+	}
+
+	// Collection struct is used to store the owner and mint settings of a group of kitties.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Collection<T: Config> {
+		pub owner: T::AccountId,
+		/// The most kitties that can ever be minted into the collection, if limited.
+		pub max_supply: Option<u32>,
+		/// The number of kitties minted into the collection so far, burned ones included.
+		pub minted: u32,
+		/// The amount paid to the owner for each kitty minted by anyone but the owner and admins.
+		pub mint_price: BalanceOf<T>,
+		pub mint_policy: MintPolicy,
+		/// Once set, the names, URIs and attributes of the collection's kitties cannot change.
+		pub metadata_frozen: bool,
+	}
+
+	// Auction struct is used to store a running English auction for a kitty.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Auction<T: Config> {
+		pub seller: T::AccountId,
+		/// The lowest bid the seller accepts.
+		pub reserve: BalanceOf<T>,
+		/// The block in which the auction is settled.
+		pub end: T::BlockNumber,
+		/// The highest bid so far and who placed it. The amount is reserved from the bidder.
+		pub best_bid: Option<(T::AccountId, BalanceOf<T>)>,
+	}
+
+	// Offer struct is used to store a bid on a kitty that need not be for sale.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Offer<T: Config> {
+		/// The amount offered. It is reserved from the bidder until the offer ends.
+		pub amount: BalanceOf<T>,
+		/// The first block in which the offer can no longer be accepted.
+		pub expiry: T::BlockNumber,
+		/// The `mint_nonce` of the kitty the offer was made for. A kitty minted later with the
+		/// same DNA, after that one was burned, cannot be sold through the offer.
+		pub kitty_nonce: u64,
+	}
+
+	// Commitment struct is used to store a hidden DNA until its owner reveals and mints it.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Commitment<T: Config> {
+		/// The amount reserved from the committer until the commitment is revealed or expires.
+		pub deposit: BalanceOf<T>,
+		/// The block the commitment was made in. It can be revealed from the next block on.
+		pub committed_at: T::BlockNumber,
+		/// The block at whose start the commitment expires if it has not been revealed.
+		pub expiry: T::BlockNumber,
+	}
+
+	// Lease struct is used to store the right to use a kitty that stays owned by someone else.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct Lease<T: Config> {
+		/// The account that may use the kitty while the lease runs.
+		pub borrower: T::AccountId,
+		/// The block at whose start the lease ends.
+		pub until: T::BlockNumber,
+		/// The amount the borrower pays the owner when the lease starts.
+		pub fee: BalanceOf<T>,
+	}
+
+	// KittyMetadata struct is used to store a kitty's name and off-chain URI.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct KittyMetadata<T: Config> {
+		pub name: KittyName<T>,
+		pub uri: KittyUri<T>,
+		/// The account the deposit is reserved from, which may be a previous owner.
+		pub depositor: T::AccountId,
+		pub deposit: BalanceOf<T>,
+	}
+
+	// KittyAttribute struct is used to store the value of one attribute of a kitty.
+	#[derive(TypeInfo, Encode, Decode, MaxEncodedLen, Clone, PartialEq, RuntimeDebug)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct KittyAttribute<T: Config> {
+		pub value: AttributeValue<T>,
+		/// The account the deposit is reserved from, which may be a previous owner.
+		pub depositor: T::AccountId,
+		pub deposit: BalanceOf<T>,
+	}
+
+	// MetadataInfo is the metadata of a kitty as returned by the runtime API.
+	#[derive(TypeInfo, Encode, Decode, Default, Clone, PartialEq, RuntimeDebug)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub struct MetadataInfo {
+		/// Empty if no name is set.
+		pub name: Vec<u8>,
+		/// Empty if no URI is set.
+		pub uri: Vec<u8>,
+		/// Every attribute as `(key, value)`, in storage order.
+		pub attributes: Vec<(Vec<u8>, Vec<u8>)>,
+	}
+
 	/// Configure the pallet by specifying the parameters and types on which it depends.
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		/// Because this pallet emits events, it depends on the runtime's definition of an event.
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The currency kitties are priced in and paid with. Auction bids are reserved in it.
+		type Currency: ReservableCurrency<Self::AccountId>;
+
+		/// The source of randomness used to generate DNA on-chain and to mix it when breeding.
+		type KittyRandomness: Randomness<Self::Hash, Self::BlockNumber>;
+
+		/// Reads a kitty's traits, including its gender, from its DNA.
+		type GenomeDecoder: GenomeDecoder;
+
+		/// The maximum length of a kitty's DNA, in bytes.
+		#[pallet::constant]
+		type MaxDnaLen: Get<u32>;
+
+		/// The maximum number of kitties a single account can own.
+		#[pallet::constant]
+		type MaxKittiesOwned: Get<u32>;
+
+		/// The amount reserved from an account when it creates a kitty, returned when the kitty
+		/// is burned.
+		#[pallet::constant]
+		type KittyDeposit: Get<BalanceOf<Self>>;
+
+		/// The maximum number of auctions that can end in the same block.
+		#[pallet::constant]
+		type MaxAuctionsPerBlock: Get<u32>;
+
+		/// The amount reserved from an account for each DNA commitment. It is returned when the
+		/// commitment is revealed and slashed when it expires.
+		#[pallet::constant]
+		type CommitDeposit: Get<BalanceOf<Self>>;
+
+		/// The number of blocks after which an unrevealed commitment expires.
+		#[pallet::constant]
+		type CommitPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of commitments that can expire in the same block.
+		#[pallet::constant]
+		type MaxCommitsPerBlock: Get<u32>;
+
+		/// Whether owners may hand a kitty over with `change_kitty_owner` or `transfer_from`,
+		/// without the recipient accepting it.
+		#[pallet::constant]
+		type AllowDirectTransfer: Get<bool>;
+
+		/// The maximum number of offers that can expire in the same block.
+		#[pallet::constant]
+		type MaxOffersPerBlock: Get<u32>;
+
+		/// The largest royalty a creator can set on a kitty.
+		#[pallet::constant]
+		type MaxRoyalty: Get<Permill>;
+
+		/// The maximum number of leases that can end in the same block.
+		#[pallet::constant]
+		type MaxLeasesPerBlock: Get<u32>;
+
+		/// The maximum length of a kitty's name, in bytes.
+		#[pallet::constant]
+		type MaxNameLen: Get<u32>;
+
+		/// The maximum length of a kitty's metadata URI, in bytes.
+		#[pallet::constant]
+		type MaxUriLen: Get<u32>;
+
+		/// The maximum length of an attribute key, in bytes.
+		#[pallet::constant]
+		type MaxAttributeKeyLen: Get<u32>;
+
+		/// The maximum length of an attribute value, in bytes.
+		#[pallet::constant]
+		type MaxAttributeValueLen: Get<u32>;
+
+		/// The maximum number of attributes a kitty can have.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+
+		/// The base amount reserved from the owner for a kitty's name and URI.
+		#[pallet::constant]
+		type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+		/// The base amount reserved from the owner for each attribute.
+		#[pallet::constant]
+		type AttributeDepositBase: Get<BalanceOf<Self>>;
+
+		/// The amount reserved per byte of name, URI, attribute key and attribute value, on top
+		/// of the base deposits.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The amount reserved from an account when it creates a collection.
+		#[pallet::constant]
+		type CollectionDeposit: Get<BalanceOf<Self>>;
+
+		/// The number of blocks a kitty rests after breeding, indexed by its generation.
+		/// Generations past the end of the schedule use its last entry.
+		#[pallet::constant]
+		type CooldownSchedule: Get<Vec<Self::BlockNumber>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(8);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
-	#[pallet::without_storage_info]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	// The pallet's runtime storage items.
@@ -60,13 +341,283 @@ pub mod pallet {
 	// Value: kitty
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
-	pub(super) type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, Vec<u8>, Kitty<T>, OptionQuery>;
+	pub(super) type Kitties<T: Config> = StorageMap<_, Blake2_128Concat, Dna<T>, Kitty<T>, OptionQuery>;
+
+	// Key: (AccountId, dna)
+	// Value: () for every kitty the account owns
+	#[pallet::storage]
+	pub(super) type KittiesOwned<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		Dna<T>,
+		(),
+		OptionQuery,
+	>;
 
 	// Key: AccountId
-	// Value: Array of kitty DNAs
+	// Value: number of kitties the account owns
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_count_of)]
+	pub(super) type KittiesOwnedCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	// Key: dna
+	// Value: () if the kitty is listed for sale at its current price
+	#[pallet::storage]
+	#[pallet::getter(fn kitties_for_sale)]
+	pub(super) type KittiesForSale<T: Config> = StorageMap<_, Blake2_128Concat, Dna<T>, (), OptionQuery>;
+
+	// Key: dna
+	// Value: the account that paid the creation deposit and the amount reserved
+	#[pallet::storage]
+	pub(super) type KittyDeposits<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	// Key: dna
+	// Value: the account approved to transfer the kitty on the owner's behalf
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_approval)]
+	pub(super) type KittyApprovals<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, T::AccountId, OptionQuery>;
+
+	// Key: dna
+	// Value: the account the owner offered the kitty to, until it accepts or rejects it
+	#[pallet::storage]
+	#[pallet::getter(fn pending_transfer)]
+	pub(super) type PendingTransfers<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, T::AccountId, OptionQuery>;
+
+	// Key: (owner, operator)
+	// Value: () if the operator may transfer every kitty of the owner
+	#[pallet::storage]
+	pub(super) type OperatorApprovals<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	// Key: dna
+	// Value: the running auction for the kitty
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub(super) type Auctions<T: Config> = StorageMap<_, Blake2_128Concat, Dna<T>, Auction<T>, OptionQuery>;
+
+	// Key: block number
+	// Value: DNAs of the auctions settled at the start of that block
+	#[pallet::storage]
+	pub(super) type AuctionsEnding<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<Dna<T>, T::MaxAuctionsPerBlock>,
+		ValueQuery,
+	>;
+
+	// Key: (dna, bidder)
+	// Value: the bidder's open offer for the kitty
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub(super) type Offers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Dna<T>,
+		Blake2_128Concat,
+		T::AccountId,
+		Offer<T>,
+		OptionQuery,
+	>;
+
+	// Key: block number
+	// Value: offers that expire in that block
+	#[pallet::storage]
+	pub(super) type OffersExpiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(Dna<T>, T::AccountId), T::MaxOffersPerBlock>,
+		ValueQuery,
+	>;
+
+	// The first block whose expiring offers have not been refunded yet.
+	#[pallet::storage]
+	pub(super) type OfferExpiryCursor<T: Config> = StorageValue<_, T::BlockNumber, ValueQuery>;
+
+	// Key: (committer, hash of (committer, dna, salt))
+	// Value: commitment waiting to be revealed
+	#[pallet::storage]
+	#[pallet::getter(fn commitments)]
+	pub(super) type Commitments<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::Hash,
+		Commitment<T>,
+		OptionQuery,
+	>;
+
+	// Key: block number
+	// Value: commitments that expire at the start of that block
+	#[pallet::storage]
+	pub(super) type CommitmentsExpiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(T::AccountId, T::Hash), T::MaxCommitsPerBlock>,
+		ValueQuery,
+	>;
+
+	// Key: dna
+	// Value: the lease the owner offered, until the borrower accepts it
+	#[pallet::storage]
+	#[pallet::getter(fn lease_offer)]
+	pub(super) type LeaseOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, Lease<T>, OptionQuery>;
+
+	// Key: dna
+	// Value: the running lease of the kitty
+	#[pallet::storage]
+	#[pallet::getter(fn leases)]
+	pub(super) type Leases<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, Lease<T>, OptionQuery>;
+
+	// Key: block number
+	// Value: DNAs of the leases that end at the start of that block
+	#[pallet::storage]
+	pub(super) type LeasesExpiring<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<Dna<T>, T::MaxLeasesPerBlock>,
+		ValueQuery,
+	>;
+
+	// Key: dna
+	// Value: the kitty's name and URI, with the deposit held for them
+	#[pallet::storage]
+	#[pallet::getter(fn metadata_of)]
+	pub(super) type Metadata<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, KittyMetadata<T>, OptionQuery>;
+
+	// Key: (dna, attribute key)
+	// Value: the attribute's value, with the deposit held for it
+	#[pallet::storage]
+	pub(super) type Attributes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		Dna<T>,
+		Blake2_128Concat,
+		AttributeKey<T>,
+		KittyAttribute<T>,
+		OptionQuery,
+	>;
+
+	// Key: dna
+	// Value: the number of attributes the kitty has
+	#[pallet::storage]
+	pub(super) type AttributeCount<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, u32, ValueQuery>;
+
+	// Key: collection id
+	// Value: the collection's owner and mint settings
+	#[pallet::storage]
+	#[pallet::getter(fn collections)]
+	pub(super) type Collections<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, Collection<T>, OptionQuery>;
+
+	// The id the next collection is created with.
+	#[pallet::storage]
+	pub(super) type NextCollectionId<T> = StorageValue<_, CollectionId, ValueQuery>;
+
+	// Key: collection id
+	// Value: the number of kitties in the collection that have not been burned
+	#[pallet::storage]
+	#[pallet::getter(fn collection_supply)]
+	pub(super) type CollectionSupply<T> =
+		StorageMap<_, Twox64Concat, CollectionId, u32, ValueQuery>;
+
+	// Key: collection id
+	// Value: the account that paid the collection deposit and the amount reserved
+	#[pallet::storage]
+	pub(super) type CollectionDeposits<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	// Key: (collection id, AccountId)
+	// Value: () if the account is an admin of the collection
+	#[pallet::storage]
+	pub(super) type CollectionAdmins<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	// Key: (collection id, AccountId)
+	// Value: () if the account may mint into the collection under `MintPolicy::AllowList`
+	#[pallet::storage]
+	pub(super) type CollectionAllowList<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
+	// Key: dna
+	// Value: () if the owner locked the kitty against changing owner
+	#[pallet::storage]
+	pub(super) type LockedKitties<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, (), OptionQuery>;
+
+	// Key: dna
+	// Value: () if root froze the kitty against changing owner
+	#[pallet::storage]
+	pub(super) type FrozenKitties<T: Config> =
+		StorageMap<_, Blake2_128Concat, Dna<T>, (), OptionQuery>;
+
+	// The `mint_nonce` of the next kitty minted.
 	#[pallet::storage]
-	#[pallet::getter(fn kitties_by_owner)]
-	pub(super) type KittiesOwned<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<Vec<u8>>, OptionQuery>;
+	pub(super) type NextMintNonce<T> = StorageValue<_, u64, ValueQuery>;
+
+	// Incremented on every random draw so that draws within the same block differ.
+	#[pallet::storage]
+	pub(super) type Nonce<T> = StorageValue<_, u64, ValueQuery>;
+
+	// Kitties to mint when the chain starts.
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initial kitties as `(owner, dna, price)`. A zero price leaves the kitty unpriced.
+		pub kitties: Vec<(T::AccountId, Vec<u8>, BalanceOf<T>)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { kitties: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (owner, dna, price) in &self.kitties {
+				let dna = Pallet::<T>::to_dna(dna.clone()).expect("genesis kitty DNA is too long");
+				Pallet::<T>::mint(owner, dna, *price, None, 0, Permill::zero(), None)
+					.expect("genesis kitty is invalid");
+			}
+		}
+	}
 
 	// Pallets use events to inform users when important changes are made.
 	// https://docs.substrate.io/v3/runtime/events-and-errors
@@ -75,11 +626,183 @@ pub mod pallet {
 	pub enum Event<T: Config> {
 		// Kitty created.
 		// Parameter: [dna, who, price].
-		KittyStored(Vec<u8>, u32),
+		KittyStored(Vec<u8>, BalanceOf<T>),
+
+		// Traits decoded from a new kitty's DNA.
+		// Parameter: [dna, genome].
+		KittyGenome(Vec<u8>, Genome),
 
 		// Kitty changed owner.
 		// Parameter: [dna, old_owner, new_owner].
 		KittyChangedOwner(Vec<u8>, T::AccountId, T::AccountId),
+
+		// Kitty price changed.
+		// Parameter: [dna, price].
+		KittyPriceSet(Vec<u8>, BalanceOf<T>),
+
+		// Kitty listed for sale.
+		// Parameter: [dna, price].
+		KittyListed(Vec<u8>, BalanceOf<T>),
+
+		// Kitty removed from sale.
+		// Parameter: [dna].
+		KittyDelisted(Vec<u8>),
+
+		// Kitty sold.
+		// Parameter: [dna, seller, buyer, price].
+		KittySold(Vec<u8>, T::AccountId, T::AccountId, BalanceOf<T>),
+
+		// Kitty bred from two parents.
+		// Parameter: [dna, owner, parent_a, parent_b].
+		KittyBred(Vec<u8>, T::AccountId, Vec<u8>, Vec<u8>),
+
+		// Kitty burned.
+		// Parameter: [dna, owner].
+		KittyBurned(Vec<u8>, T::AccountId),
+
+		// Account approved to transfer a kitty.
+		// Parameter: [dna, owner, operator].
+		KittyApproved(Vec<u8>, T::AccountId, T::AccountId),
+
+		// Operator approved or revoked for all kitties of an owner.
+		// Parameter: [owner, operator, approved].
+		ApprovalForAll(T::AccountId, T::AccountId, bool),
+
+		// Kitty put up for auction.
+		// Parameter: [dna, seller, reserve, end].
+		AuctionCreated(Vec<u8>, T::AccountId, BalanceOf<T>, T::BlockNumber),
+
+		// New best bid on an auction.
+		// Parameter: [dna, bidder, amount].
+		BidPlaced(Vec<u8>, T::AccountId, BalanceOf<T>),
+
+		// Auction ended and the kitty went to the highest bidder.
+		// Parameter: [dna, seller, winner, amount].
+		AuctionSettled(Vec<u8>, T::AccountId, T::AccountId, BalanceOf<T>),
+
+		// Auction ended without a sale; any bid was refunded.
+		// Parameter: [dna].
+		AuctionCancelled(Vec<u8>),
+
+		// DNA committed to without revealing it.
+		// Parameter: [who, hash, expiry].
+		KittyCommitted(T::AccountId, T::Hash, T::BlockNumber),
+
+		// Commitment expired unrevealed and its deposit was slashed.
+		// Parameter: [who, hash, deposit].
+		CommitmentExpired(T::AccountId, T::Hash, BalanceOf<T>),
+
+		// Commitment revealed after its DNA was minted by someone else; its deposit was returned.
+		// Parameter: [who, hash, deposit].
+		CommitmentRefunded(T::AccountId, T::Hash, BalanceOf<T>),
+
+		// Kitty offered to another account, pending its acceptance.
+		// Parameter: [dna, owner, to].
+		TransferOffered(Vec<u8>, T::AccountId, T::AccountId),
+
+		// Transfer offer turned down by its recipient.
+		// Parameter: [dna, owner, to].
+		TransferRejected(Vec<u8>, T::AccountId, T::AccountId),
+
+		// Transfer offer withdrawn by the owner.
+		// Parameter: [dna, owner, to].
+		TransferCancelled(Vec<u8>, T::AccountId, T::AccountId),
+
+		// Offer made on a kitty.
+		// Parameter: [dna, bidder, amount, expiry].
+		OfferMade(Vec<u8>, T::AccountId, BalanceOf<T>, T::BlockNumber),
+
+		// Offer withdrawn by its bidder and refunded.
+		// Parameter: [dna, bidder].
+		OfferWithdrawn(Vec<u8>, T::AccountId),
+
+		// Offer expired and was refunded.
+		// Parameter: [dna, bidder].
+		OfferExpired(Vec<u8>, T::AccountId),
+
+		// Royalty paid to the creator of a sold kitty.
+		// Parameter: [dna, creator, amount].
+		RoyaltyPaid(Vec<u8>, T::AccountId, BalanceOf<T>),
+
+		// Lease of a kitty offered to a borrower.
+		// Parameter: [dna, owner, borrower, until, fee].
+		LeaseOffered(Vec<u8>, T::AccountId, T::AccountId, T::BlockNumber, BalanceOf<T>),
+
+		// Lease offer withdrawn by the owner.
+		// Parameter: [dna, owner, borrower].
+		LeaseCancelled(Vec<u8>, T::AccountId, T::AccountId),
+
+		// Lease accepted and started.
+		// Parameter: [dna, owner, borrower, until].
+		KittyLeased(Vec<u8>, T::AccountId, T::AccountId, T::BlockNumber),
+
+		// Lease ended and the kitty went back to its owner's use.
+		// Parameter: [dna, borrower].
+		LeaseEnded(Vec<u8>, T::AccountId),
+
+		// Kitty name set.
+		// Parameter: [dna, name].
+		KittyNamed(Vec<u8>, Vec<u8>),
+
+		// Kitty metadata URI set.
+		// Parameter: [dna, uri].
+		MetadataSet(Vec<u8>, Vec<u8>),
+
+		// Kitty name and metadata URI cleared.
+		// Parameter: [dna].
+		MetadataCleared(Vec<u8>),
+
+		// Kitty attribute set.
+		// Parameter: [dna, key, value].
+		AttributeSet(Vec<u8>, Vec<u8>, Vec<u8>),
+
+		// Kitty attribute cleared.
+		// Parameter: [dna, key].
+		AttributeCleared(Vec<u8>, Vec<u8>),
+
+		// Collection created.
+		// Parameter: [collection, owner].
+		CollectionCreated(CollectionId, T::AccountId),
+
+		// Empty collection destroyed and its deposit returned.
+		// Parameter: [collection, owner].
+		CollectionDestroyed(CollectionId, T::AccountId),
+
+		// Collection mint settings changed.
+		// Parameter: [collection, max_supply, mint_price, mint_policy].
+		CollectionMintSet(CollectionId, Option<u32>, BalanceOf<T>, MintPolicy),
+
+		// Account made or removed as an admin of a collection.
+		// Parameter: [collection, who, is_admin].
+		CollectionAdminSet(CollectionId, T::AccountId, bool),
+
+		// Account added to or removed from the allow-list of a collection.
+		// Parameter: [collection, who, allowed].
+		AllowListSet(CollectionId, T::AccountId, bool),
+
+		// Metadata of the kitties in a collection frozen.
+		// Parameter: [collection].
+		CollectionMetadataFrozen(CollectionId),
+
+		// Kitty minted into a collection.
+		// Parameter: [dna, collection, who].
+		KittyMinted(Vec<u8>, CollectionId, T::AccountId),
+
+		// Kitty locked by its owner.
+		// Parameter: [dna, owner].
+		KittyLocked(Vec<u8>, T::AccountId),
+
+		// Kitty unlocked by its owner.
+		// Parameter: [dna, owner].
+		KittyUnlocked(Vec<u8>, T::AccountId),
+
+		// Kitty frozen by root.
+		// Parameter: [dna].
+		KittyFrozen(Vec<u8>),
+
+		// Kitty thawed by root.
+		// Parameter: [dna].
+		KittyThawed(Vec<u8>),
 	}
 
 	// Errors inform users that something went wrong.
@@ -91,6 +814,188 @@ pub mod pallet {
 		KittyAlreadyExist,
 		KittyNotOwned,
 		KittyNotConfiguredPrice,
+		/// The kitty is not listed for sale.
+		KittyNotForSale,
+		/// The buyer already owns the kitty.
+		BuyerIsKittyOwner,
+		/// The kitty costs more than the buyer is willing to pay.
+		KittyBidPriceTooLow,
+		/// Only a male and a female kitty can be bred together.
+		KittySameGender,
+		/// The DNA is longer than `MaxDnaLen`.
+		DnaTooLong,
+		/// The account already owns `MaxKittiesOwned` kitties.
+		TooManyKittiesOwned,
+		/// The kitty is being auctioned.
+		KittyInAuction,
+		/// The kitty is not being auctioned.
+		AuctionNotExist,
+		/// An auction must last at least one block.
+		InvalidAuctionDuration,
+		/// The bid is below the reserve or does not beat the best bid.
+		BidTooLow,
+		/// The end block already has `MaxAuctionsPerBlock` auctions ending in it.
+		TooManyAuctionsEnding,
+		/// The caller neither owns the kitty nor is approved to transfer it.
+		NotApproved,
+		/// The caller already has an unrevealed commitment with this hash.
+		CommitmentAlreadyExist,
+		/// The caller has no unrevealed commitment for this DNA and salt.
+		CommitmentNotExist,
+		/// A commitment can only be revealed in a block after the one it was made in.
+		RevealTooEarly,
+		/// The expiry block already has `MaxCommitsPerBlock` commitments expiring in it.
+		TooManyCommitmentsExpiring,
+		/// The kitty has been offered to another account and is waiting for it to accept.
+		TransferPending,
+		/// The kitty has not been offered to anyone.
+		TransferNotExist,
+		/// The kitty was offered to a different account.
+		NotTransferRecipient,
+		/// The kitty cannot be offered to its own owner.
+		TransferToSelf,
+		/// Kitties must be offered with `offer_transfer` and accepted by the recipient.
+		DirectTransferDisabled,
+		/// The bidder already has an offer on the kitty.
+		OfferAlreadyExist,
+		/// The bidder has no offer on the kitty.
+		OfferNotExist,
+		/// An offer must expire after the current block.
+		InvalidOfferExpiry,
+		/// The offer has expired.
+		OfferExpired,
+		/// The expiry block already has `MaxOffersPerBlock` offers expiring in it.
+		TooManyOffersExpiring,
+		/// The royalty is above `MaxRoyalty`.
+		RoyaltyTooHigh,
+		/// The kitty is leased and cannot change owner until the lease ends.
+		KittyLeased,
+		/// There is no lease offer for the kitty.
+		LeaseNotExist,
+		/// Only the borrower named in the lease offer can accept it.
+		NotLeaseBorrower,
+		/// A kitty cannot be lent to its owner.
+		LendToSelf,
+		/// The lease must end after the current block.
+		InvalidLeaseExpiry,
+		/// The end block already has `MaxLeasesPerBlock` leases ending in it.
+		TooManyLeasesExpiring,
+		/// The name is longer than `MaxNameLen`.
+		NameTooLong,
+		/// The URI is longer than `MaxUriLen`.
+		UriTooLong,
+		/// The attribute key is longer than `MaxAttributeKeyLen`.
+		AttributeKeyTooLong,
+		/// The attribute value is longer than `MaxAttributeValueLen`.
+		AttributeValueTooLong,
+		/// The kitty already has `MaxAttributes` attributes.
+		TooManyAttributes,
+		/// The kitty has no name or URI.
+		MetadataNotExist,
+		/// The kitty has no attribute with the key.
+		AttributeNotExist,
+		/// There is no collection with the id.
+		CollectionNotExist,
+		/// Every collection id is taken.
+		NoAvailableCollectionId,
+		/// The caller does not own the collection.
+		NotCollectionOwner,
+		/// The caller is neither the owner nor an admin of the collection.
+		NotCollectionAdmin,
+		/// The collection's mint policy does not let the caller mint into it.
+		MintNotAllowed,
+		/// The collection already has `max_supply` kitties minted into it.
+		CollectionFull,
+		/// The max supply is below the number of kitties already minted into the collection.
+		MaxSupplyTooLow,
+		/// The kitty's collection has frozen its metadata.
+		MetadataFrozen,
+		/// A parent is still resting from its last breeding.
+		KittyOnCooldown,
+		/// The kitty is locked by its owner or frozen by root and cannot change owner.
+		Frozen,
+		/// The kitty is not locked.
+		KittyNotLocked,
+		/// The kitty is not frozen.
+		KittyNotFrozen,
+		/// The offer was made for an earlier kitty with the same DNA, before it was burned.
+		OfferOutdated,
+		/// Less than the sale amount is still reserved from the buyer.
+		PaymentNotReserved,
+		/// The collection still has kitties that have not been burned.
+		CollectionNotEmpty,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		// Settle every auction that ends in this block, expire unrevealed commitments and end
+		// leases.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let ending = <AuctionsEnding<T>>::take(now);
+			let count = ending.len() as u32;
+			for dna in ending {
+				Self::settle_auction(dna);
+			}
+
+			let expiring = <CommitmentsExpiring<T>>::take(now);
+			let expired = expiring.len() as u32;
+			for (who, hash) in expiring {
+				Self::expire_commitment(who, hash);
+			}
+
+			let leases = <LeasesExpiring<T>>::take(now);
+			let ended = leases.len() as u32;
+			for dna in leases {
+				Self::end_lease(dna);
+			}
+
+			T::WeightInfo::on_initialize(count)
+				.saturating_add(T::WeightInfo::expire_commitments(expired))
+				.saturating_add(T::WeightInfo::expire_leases(ended))
+		}
+
+		// Refund expired offers, a block at a time and oldest first, with the weight left over.
+		fn on_idle(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let mut cursor = <OfferExpiryCursor<T>>::get();
+			// The first run has nothing to catch up on: offers always expire after the block
+			// they are made in.
+			if cursor.is_zero() {
+				cursor = now;
+			}
+
+			let mut used: Weight = 0;
+			while cursor <= now {
+				let expiring = <OffersExpiring<T>>::get(cursor);
+				let weight = T::WeightInfo::expire_offers(expiring.len() as u32);
+				if used.saturating_add(weight) > remaining_weight {
+					break
+				}
+
+				<OffersExpiring<T>>::remove(cursor);
+				for (dna, bidder) in expiring {
+					Self::expire_offer(dna, bidder);
+				}
+				used = used.saturating_add(weight);
+				cursor = cursor.saturating_add(One::one());
+			}
+
+			<OfferExpiryCursor<T>>::put(cursor);
+			used
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			let errors = Self::integrity_errors();
+			if !errors.is_empty() {
+				frame_support::log::error!(
+					target: "runtime::kitties",
+					"integrity errors: {:?}",
+					errors,
+				);
+				return Err("kitty storage is inconsistent")
+			}
+			Ok(())
+		}
 	}
 
 	// Dispatchable functions allows users to interact with the pallet and invoke state changes.
@@ -98,49 +1003,40 @@ pub mod pallet {
 	// Dispatchable functions must be annotated with a weight and must return a DispatchResult.
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
-		/// An example dispatchable that takes a singles value as a parameter, writes the value to
-		/// storage and emits an event. This function must be dispatched by a signed extrinsic.
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn create_kitty(origin: OriginFor<T>, dna: Vec<u8>, price: u32) -> DispatchResult {
+		/// Mint a new kitty with the given DNA for the caller, priced at `price`.
+		///
+		/// The price must not be zero; the kitty is not listed for sale until `list_for_sale`
+		/// is called. `KittyDeposit` is reserved from the caller until the kitty is burned.
+		///
+		/// The caller is recorded as the kitty's creator and receives `royalty` of the price of
+		/// every later sale, up to `MaxRoyalty`.
+		///
+		/// The DNA must have been committed to with `commit_kitty` in an earlier block, using the
+		/// same `salt`, and the commitment deposit is returned as for `reveal_kitty`.
+		#[pallet::weight(T::WeightInfo::create_kitty(dna.len() as u32))]
+		#[transactional]
+		pub fn create_kitty(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			salt: [u8; 32],
+			price: BalanceOf<T>,
+			royalty: Permill,
+		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
 
-			let kitty = <Kitties<T>>::get(dna.clone());
-			ensure!(kitty.is_none(), Error::<T>::KittyAlreadyExist);
-
-			ensure!(price > 0, Error::<T>::KittyNotConfiguredPrice);
-
-			let owner = who.clone();
-
-			let gender = Self::gen_gender(dna.clone())?;
-			let kitty = Kitty {
-				dna: dna.clone(),
-				gender: gender,
-				price: price,
-				owner: owner,
-			};
+			ensure!(!price.is_zero(), Error::<T>::KittyNotConfiguredPrice);
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
 
 			// Update storage.
-			<Kitties<T>>::insert(dna.clone(), kitty);
-			
-			let mut current_number_kitties = <NumOfKitties<T>>::get();
-			current_number_kitties += 1;
-			NumOfKitties::<T>::put(current_number_kitties);
-
-			let current_user_kitties = <KittiesOwned<T>>::get(&who);
-			match current_user_kitties {
-				Some(mut kitties) => {
-					kitties.push(dna.clone());
-					<KittiesOwned<T>>::insert(&who, kitties);
-				},
-				None => {
-					let mut kitties = Vec::new();
-					kitties.push(dna.clone());
-					<KittiesOwned<T>>::insert(&who, kitties);
-				},
-			};
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			if !Self::take_commitment(&who, &dna, &bounded_dna, &salt)? {
+				return Ok(())
+			}
+			Self::mint(&who, bounded_dna.clone(), price, None, 0, royalty, None)?;
+			Self::reserve_deposit(&who, &bounded_dna)?;
 
 			// Emit an event.
 			Self::deposit_event(Event::KittyStored(dna, price));
@@ -148,63 +1044,1597 @@ pub mod pallet {
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(T::WeightInfo::change_kitty_owner())]
 		pub fn change_kitty_owner(origin: OriginFor<T>, dna: Vec<u8>, new_owner: T::AccountId) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
 			let who = ensure_signed(origin)?;
 			let owner = who.clone();
+			ensure!(T::AllowDirectTransfer::get(), Error::<T>::DirectTransferDisabled);
 
-			let kitty_opt = <Kitties<T>>::get(dna.clone());
-			ensure!(kitty_opt.is_some(), Error::<T>::KittyNotExist);
-
-			let mut kitty = kitty_opt.unwrap();
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
 			ensure!(kitty.owner == owner, Error::<T>::KittyNotOwned);
+			Self::ensure_transferable(&bounded_dna)?;
 
-			let current_owner_kitties = <KittiesOwned<T>>::get(&who);
-			match current_owner_kitties {
-				Some(mut kitties) => {
-					let index = kitties.iter().position(|x| x == &dna).unwrap();
-					kitties.remove(index);
-					<KittiesOwned<T>>::insert(&who, kitties);
-				},
-				None => {
-					Err(Error::<T>::KittyNotOwned)?;
-				},
-			};
-
-			let current_new_owner_kitties = <KittiesOwned<T>>::get(&new_owner);
-			match current_new_owner_kitties {
-				Some(mut kitties) => {
-					kitties.push(dna.clone());
-					<KittiesOwned<T>>::insert(&new_owner, kitties);
-				},
-				None => {
-					let mut kitties = Vec::new();
-					kitties.push(dna.clone());
-					<KittiesOwned<T>>::insert(&new_owner, kitties);
-				},
-			};
-
-			kitty.owner = new_owner.clone();
-			<Kitties<T>>::insert(dna.clone(), kitty);
+			Self::transfer_kitty(&bounded_dna, kitty, &new_owner)?;
 
 			// Emit an event.
 			Self::deposit_event(Event::KittyChangedOwner(dna, owner, new_owner));
 			// Return a successful DispatchResultWithPostInfo
 			Ok(())
 		}
-	}
-}
 
-// helper functions
-impl<T> Pallet<T> {
-	fn gen_gender(dna: Vec<u8>) -> Result<Gender, Error<T>>{
-		let mut res = Gender::Female;
-		if dna.len() % 2 ==0 {
-			res = Gender::Male;
+		/// Set the price the kitty will be sold at once it is listed.
+		#[pallet::weight(T::WeightInfo::set_price())]
+		pub fn set_price(origin: OriginFor<T>, dna: Vec<u8>, price: BalanceOf<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let mut kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(!price.is_zero(), Error::<T>::KittyNotConfiguredPrice);
+
+			kitty.price = price;
+			<Kitties<T>>::insert(&bounded_dna, kitty);
+
+			Self::deposit_event(Event::KittyPriceSet(dna, price));
+			Ok(())
+		}
+
+		/// Put the kitty up for sale at its configured price.
+		#[pallet::weight(T::WeightInfo::list_for_sale())]
+		pub fn list_for_sale(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(!kitty.price.is_zero(), Error::<T>::KittyNotConfiguredPrice);
+			Self::ensure_transferable(&bounded_dna)?;
+
+			<KittiesForSale<T>>::insert(&bounded_dna, ());
+
+			Self::deposit_event(Event::KittyListed(dna, kitty.price));
+			Ok(())
+		}
+
+		/// Take the kitty off the market.
+		#[pallet::weight(T::WeightInfo::delist())]
+		pub fn delist(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(<KittiesForSale<T>>::contains_key(&bounded_dna), Error::<T>::KittyNotForSale);
+
+			<KittiesForSale<T>>::remove(&bounded_dna);
+
+			Self::deposit_event(Event::KittyDelisted(dna));
+			Ok(())
+		}
+
+		/// Buy a listed kitty, paying its price to the current owner.
+		///
+		/// The call fails rather than paying more than `max_price`, so a seller cannot raise the
+		/// price under a pending purchase.
+		#[pallet::weight(T::WeightInfo::buy_kitty())]
+		#[transactional]
+		pub fn buy_kitty(origin: OriginFor<T>, dna: Vec<u8>, max_price: BalanceOf<T>) -> DispatchResult {
+			let buyer = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(<KittiesForSale<T>>::contains_key(&bounded_dna), Error::<T>::KittyNotForSale);
+			ensure!(kitty.owner != buyer, Error::<T>::BuyerIsKittyOwner);
+			ensure!(kitty.price <= max_price, Error::<T>::KittyBidPriceTooLow);
+
+			let seller = kitty.owner.clone();
+			let price = kitty.price;
+
+			Self::pay_sale(&bounded_dna, &kitty, &buyer, &seller, price)?;
+			Self::transfer_kitty(&bounded_dna, kitty, &buyer)?;
+
+			Self::deposit_event(Event::KittySold(dna, seller, buyer, price));
+			Ok(())
+		}
+
+		/// Breed a male and a female kitty into a new kitty owned by the caller.
+		///
+		/// The caller must own each parent, be approved for it or be an operator of its owner.
+		/// The parents stay with their owners, while the child goes to the caller, who also pays
+		/// its `KittyDeposit`.
+		///
+		/// The child's DNA mixes the parents' DNA with on-chain randomness, and its generation is
+		/// one above the older parent's. Each parent then rests for the `CooldownSchedule` entry
+		/// of its generation before it can breed again. Locked, frozen or leased kitties cannot
+		/// breed, so that the owner does not use up a borrower's cooldown.
+		#[pallet::weight(T::WeightInfo::breed_kitty(parent_a.len().max(parent_b.len()) as u32))]
+		#[transactional]
+		pub fn breed_kitty(origin: OriginFor<T>, parent_a: Vec<u8>, parent_b: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let kitty_a = <Kitties<T>>::get(Self::to_dna(parent_a.clone())?)
+				.ok_or(Error::<T>::KittyNotExist)?;
+			let kitty_b = <Kitties<T>>::get(Self::to_dna(parent_b.clone())?)
+				.ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(
+				Self::is_approved_or_owner(&kitty_a.dna, &kitty_a, &who) &&
+					Self::is_approved_or_owner(&kitty_b.dna, &kitty_b, &who),
+				Error::<T>::NotApproved
+			);
+			ensure!(kitty_a.gender != kitty_b.gender, Error::<T>::KittySameGender);
+			Self::ensure_not_frozen(&kitty_a.dna)?;
+			Self::ensure_not_frozen(&kitty_b.dna)?;
+			ensure!(
+				!<Leases<T>>::contains_key(&kitty_a.dna) &&
+					!<Leases<T>>::contains_key(&kitty_b.dna),
+				Error::<T>::KittyLeased
+			);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(
+				kitty_a.cooldown_until <= now && kitty_b.cooldown_until <= now,
+				Error::<T>::KittyOnCooldown
+			);
+			ensure!(
+				!<PendingTransfers<T>>::contains_key(&kitty_a.dna) &&
+					!<PendingTransfers<T>>::contains_key(&kitty_b.dna),
+				Error::<T>::TransferPending
+			);
+
+			let dna = Self::gen_child_dna(&who, &parent_a, &parent_b);
+			let generation = kitty_a.generation.max(kitty_b.generation).saturating_add(1);
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			Self::mint(
+				&who,
+				bounded_dna.clone(),
+				Zero::zero(),
+				Some((kitty_a.dna.clone(), kitty_b.dna.clone())),
+				generation,
+				Permill::zero(),
+				None,
+			)?;
+			Self::reserve_deposit(&who, &bounded_dna)?;
+			for mut parent in [kitty_a, kitty_b] {
+				parent.cooldown_until = now.saturating_add(Self::cooldown_of(parent.generation));
+				<Kitties<T>>::insert(parent.dna.clone(), parent);
+			}
+
+			Self::deposit_event(Event::KittyBred(dna, who, parent_a, parent_b));
+			Ok(())
+		}
+
+		/// Mint a kitty whose DNA is generated on-chain, so the caller cannot pick it.
+		///
+		/// The kitty starts without a price; use `set_price` before listing it. The caller is
+		/// recorded as its creator and receives `royalty` of every later sale, up to `MaxRoyalty`.
+		#[pallet::weight(T::WeightInfo::mint_random_kitty())]
+		#[transactional]
+		pub fn mint_random_kitty(origin: OriginFor<T>, royalty: Permill) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+			let dna = Self::random_hash(&who).as_ref().to_vec();
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			Self::mint(&who, bounded_dna.clone(), Zero::zero(), None, 0, royalty, None)?;
+			Self::reserve_deposit(&who, &bounded_dna)?;
+
+			Self::deposit_event(Event::KittyStored(dna, Zero::zero()));
+			Ok(())
 		}
-		Ok(res)
+
+		/// Destroy a kitty owned by the caller and refund its creation and metadata deposits.
+		///
+		/// The DNA becomes free to be minted again.
+		#[pallet::weight(T::WeightInfo::burn_kitty(T::MaxAttributes::get()))]
+		pub fn burn_kitty(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna)?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_transferable(&bounded_dna)?;
+
+			Self::burn(&bounded_dna, kitty)
+		}
+
+		/// Destroy any kitty. A running auction for it is cancelled and its best bid refunded.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::force_burn(T::MaxAttributes::get()))]
+		pub fn force_burn(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let bounded_dna = Self::to_dna(dna)?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			Self::cancel_auction(&bounded_dna);
+
+			Self::burn(&bounded_dna, kitty)
+		}
+
+		/// Approve `operator` to transfer the kitty once on the owner's behalf.
+		///
+		/// The owner or one of the owner's operators may call this. The approval is cleared when
+		/// the kitty changes owner.
+		#[pallet::weight(T::WeightInfo::approve())]
+		pub fn approve(origin: OriginFor<T>, dna: Vec<u8>, operator: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(
+				kitty.owner == who || Self::is_approved_for_all(&kitty.owner, &who),
+				Error::<T>::KittyNotOwned
+			);
+
+			<KittyApprovals<T>>::insert(&bounded_dna, &operator);
+
+			Self::deposit_event(Event::KittyApproved(dna, kitty.owner, operator));
+			Ok(())
+		}
+
+		/// Allow or forbid `operator` to transfer any kitty the caller owns, now or later.
+		#[pallet::weight(T::WeightInfo::set_approval_for_all())]
+		pub fn set_approval_for_all(
+			origin: OriginFor<T>,
+			operator: T::AccountId,
+			approved: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			if approved {
+				<OperatorApprovals<T>>::insert(&who, &operator, ());
+			} else {
+				<OperatorApprovals<T>>::remove(&who, &operator);
+			}
+
+			Self::deposit_event(Event::ApprovalForAll(who, operator, approved));
+			Ok(())
+		}
+
+		/// Transfer a kitty to `to` as its owner, its approved account or an operator of its
+		/// owner.
+		#[pallet::weight(T::WeightInfo::transfer_from())]
+		pub fn transfer_from(origin: OriginFor<T>, dna: Vec<u8>, to: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(T::AllowDirectTransfer::get(), Error::<T>::DirectTransferDisabled);
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(Self::is_approved_or_owner(&bounded_dna, &kitty, &who), Error::<T>::NotApproved);
+			Self::ensure_transferable(&bounded_dna)?;
+
+			let owner = kitty.owner.clone();
+			Self::transfer_kitty(&bounded_dna, kitty, &to)?;
+
+			Self::deposit_event(Event::KittyChangedOwner(dna, owner, to));
+			Ok(())
+		}
+
+		/// Put the kitty up for an English auction that ends `duration` blocks from now.
+		///
+		/// The kitty is taken off the fixed-price market and cannot be transferred until the
+		/// auction is settled. Bids must be at least `reserve`.
+		#[pallet::weight(T::WeightInfo::create_auction())]
+		pub fn create_auction(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			reserve: BalanceOf<T>,
+			duration: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_transferable(&bounded_dna)?;
+			ensure!(!duration.is_zero(), Error::<T>::InvalidAuctionDuration);
+
+			let end = <frame_system::Pallet<T>>::block_number().saturating_add(duration);
+			<AuctionsEnding<T>>::try_mutate(end, |ending| ending.try_push(bounded_dna.clone()))
+				.map_err(|_| Error::<T>::TooManyAuctionsEnding)?;
+
+			<KittiesForSale<T>>::remove(&bounded_dna);
+			<Auctions<T>>::insert(
+				&bounded_dna,
+				Auction { seller: who.clone(), reserve, end, best_bid: None },
+			);
+
+			Self::deposit_event(Event::AuctionCreated(dna, who, reserve, end));
+			Ok(())
+		}
+
+		/// Bid on a running auction.
+		///
+		/// The amount is reserved from the bidder until the auction ends or a higher bid is
+		/// placed, at which point it is returned.
+		#[pallet::weight(T::WeightInfo::bid())]
+		pub fn bid(origin: OriginFor<T>, dna: Vec<u8>, amount: BalanceOf<T>) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let mut auction = <Auctions<T>>::get(&bounded_dna).ok_or(Error::<T>::AuctionNotExist)?;
+			ensure!(auction.seller != bidder, Error::<T>::BuyerIsKittyOwner);
+			ensure!(!amount.is_zero() && amount >= auction.reserve, Error::<T>::BidTooLow);
+			if let Some((_, best)) = &auction.best_bid {
+				ensure!(amount > *best, Error::<T>::BidTooLow);
+			}
+
+			T::Currency::reserve(&bidder, amount)?;
+			if let Some((previous, best)) = auction.best_bid.take() {
+				T::Currency::unreserve(&previous, best);
+			}
+
+			auction.best_bid = Some((bidder.clone(), amount));
+			<Auctions<T>>::insert(&bounded_dna, auction);
+
+			Self::deposit_event(Event::BidPlaced(dna, bidder, amount));
+			Ok(())
+		}
+
+		/// Commit to minting a kitty without showing its DNA, so it cannot be front-run.
+		///
+		/// `hash` is `T::Hashing` of the SCALE-encoded `(who, dna, salt)`, with `dna` as bytes
+		/// and `salt` as 32 bytes. `CommitDeposit` is reserved until the kitty is minted with
+		/// `reveal_kitty`, `create_kitty` or `mint_into_collection`, which must happen in a later
+		/// block and before `CommitPeriod` blocks have passed. The deposit of a commitment that
+		/// expires is slashed.
+		#[pallet::weight(T::WeightInfo::commit_kitty())]
+		#[transactional]
+		pub fn commit_kitty(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(
+				!<Commitments<T>>::contains_key(&who, &hash),
+				Error::<T>::CommitmentAlreadyExist
+			);
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let expiry = now.saturating_add(T::CommitPeriod::get());
+			<CommitmentsExpiring<T>>::try_mutate(expiry, |expiring| {
+				expiring.try_push((who.clone(), hash))
+			})
+			.map_err(|_| Error::<T>::TooManyCommitmentsExpiring)?;
+
+			let deposit = T::CommitDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			<Commitments<T>>::insert(
+				&who,
+				&hash,
+				Commitment { deposit, committed_at: now, expiry },
+			);
+
+			Self::deposit_event(Event::KittyCommitted(who, hash, expiry));
+			Ok(())
+		}
+
+		/// Mint the kitty committed to with `commit_kitty` and return the commitment deposit.
+		///
+		/// The kitty starts without a price; use `set_price` before listing it. If the DNA was
+		/// minted by someone else in the meantime, the deposit is returned and nothing is minted.
+		///
+		/// The caller is recorded as the kitty's creator and receives `royalty` of every later
+		/// sale, up to `MaxRoyalty`.
+		#[pallet::weight(T::WeightInfo::reveal_kitty(dna.len() as u32))]
+		#[transactional]
+		pub fn reveal_kitty(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			salt: [u8; 32],
+			royalty: Permill,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			if !Self::take_commitment(&who, &dna, &bounded_dna, &salt)? {
+				return Ok(())
+			}
+
+			Self::mint(&who, bounded_dna.clone(), Zero::zero(), None, 0, royalty, None)?;
+			Self::reserve_deposit(&who, &bounded_dna)?;
+
+			Self::deposit_event(Event::KittyStored(dna, Zero::zero()));
+			Ok(())
+		}
+
+		/// Offer the kitty to `to`. It only changes owner once `to` calls `accept_transfer`.
+		///
+		/// The kitty is taken off the market, and cannot be listed, bred, auctioned or moved
+		/// otherwise, until the offer is accepted, rejected or cancelled.
+		#[pallet::weight(T::WeightInfo::offer_transfer())]
+		pub fn offer_transfer(origin: OriginFor<T>, dna: Vec<u8>, to: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(to != who, Error::<T>::TransferToSelf);
+			Self::ensure_transferable(&bounded_dna)?;
+
+			<KittiesForSale<T>>::remove(&bounded_dna);
+			<PendingTransfers<T>>::insert(&bounded_dna, &to);
+
+			Self::deposit_event(Event::TransferOffered(dna, who, to));
+			Ok(())
+		}
+
+		/// Accept a kitty offered to the caller with `offer_transfer`.
+		#[pallet::weight(T::WeightInfo::accept_transfer())]
+		pub fn accept_transfer(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let to = <PendingTransfers<T>>::get(&bounded_dna).ok_or(Error::<T>::TransferNotExist)?;
+			ensure!(to == who, Error::<T>::NotTransferRecipient);
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+
+			let owner = kitty.owner.clone();
+			Self::transfer_kitty(&bounded_dna, kitty, &who)?;
+
+			Self::deposit_event(Event::KittyChangedOwner(dna, owner, who));
+			Ok(())
+		}
+
+		/// Turn down a kitty offered to the caller. It stays with its owner.
+		#[pallet::weight(T::WeightInfo::reject_transfer())]
+		pub fn reject_transfer(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let to = <PendingTransfers<T>>::get(&bounded_dna).ok_or(Error::<T>::TransferNotExist)?;
+			ensure!(to == who, Error::<T>::NotTransferRecipient);
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+
+			<PendingTransfers<T>>::remove(&bounded_dna);
+
+			Self::deposit_event(Event::TransferRejected(dna, kitty.owner, who));
+			Ok(())
+		}
+
+		/// Withdraw a transfer offer made by the caller.
+		#[pallet::weight(T::WeightInfo::cancel_transfer())]
+		pub fn cancel_transfer(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			let to = <PendingTransfers<T>>::take(&bounded_dna).ok_or(Error::<T>::TransferNotExist)?;
+
+			Self::deposit_event(Event::TransferCancelled(dna, who, to));
+			Ok(())
+		}
+
+		/// Offer `amount` for a kitty, whether or not it is for sale.
+		///
+		/// The amount is reserved from the caller until the owner accepts the offer, the caller
+		/// withdraws it, or it reaches `expiry` and is refunded. A caller can have one offer per
+		/// kitty, next to offers from other accounts.
+		#[pallet::weight(T::WeightInfo::make_offer())]
+		#[transactional]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			amount: BalanceOf<T>,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner != bidder, Error::<T>::BuyerIsKittyOwner);
+			ensure!(!amount.is_zero(), Error::<T>::BidTooLow);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(expiry > now, Error::<T>::InvalidOfferExpiry);
+			ensure!(
+				!<Offers<T>>::contains_key(&bounded_dna, &bidder),
+				Error::<T>::OfferAlreadyExist
+			);
+
+			<OffersExpiring<T>>::try_mutate(expiry, |expiring| {
+				expiring.try_push((bounded_dna.clone(), bidder.clone()))
+			})
+			.map_err(|_| Error::<T>::TooManyOffersExpiring)?;
+			T::Currency::reserve(&bidder, amount)?;
+			let offer = Offer { amount, expiry, kitty_nonce: kitty.mint_nonce };
+			<Offers<T>>::insert(&bounded_dna, &bidder, offer);
+
+			Self::deposit_event(Event::OfferMade(dna, bidder, amount, expiry));
+			Ok(())
+		}
+
+		/// Sell the kitty to `bidder` for the amount of its offer.
+		///
+		/// Offers made for an earlier kitty with the same DNA cannot be accepted, even when it
+		/// was burned and the DNA minted again in the same block; they stay until withdrawn or
+		/// expired.
+		#[pallet::weight(T::WeightInfo::accept_offer())]
+		#[transactional]
+		pub fn accept_offer(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			bidder: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_transferable(&bounded_dna)?;
+			let offer =
+				<Offers<T>>::take(&bounded_dna, &bidder).ok_or(Error::<T>::OfferNotExist)?;
+			ensure!(
+				<frame_system::Pallet<T>>::block_number() < offer.expiry,
+				Error::<T>::OfferExpired
+			);
+			ensure!(offer.kitty_nonce == kitty.mint_nonce, Error::<T>::OfferOutdated);
+
+			Self::remove_offer_expiry(&bounded_dna, &bidder, offer.expiry);
+			Self::sell_reserved(&bounded_dna, kitty, &bidder, &who, offer.amount)?;
+
+			Self::deposit_event(Event::KittySold(dna, who, bidder, offer.amount));
+			Ok(())
+		}
+
+		/// Withdraw the caller's offer on a kitty and get the reserved amount back.
+		#[pallet::weight(T::WeightInfo::withdraw_offer())]
+		pub fn withdraw_offer(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let bidder = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let offer =
+				<Offers<T>>::take(&bounded_dna, &bidder).ok_or(Error::<T>::OfferNotExist)?;
+
+			Self::remove_offer_expiry(&bounded_dna, &bidder, offer.expiry);
+			T::Currency::unreserve(&bidder, offer.amount);
+
+			Self::deposit_event(Event::OfferWithdrawn(dna, bidder));
+			Ok(())
+		}
+
+		/// Offer to lend the kitty to `borrower` until `until_block` for `fee`.
+		///
+		/// The lease starts once `borrower` calls `accept_lease` and pays the fee. While it runs,
+		/// `borrower` is the kitty's user and the owner keeps title, but cannot sell or transfer
+		/// the kitty. A new offer replaces the previous one.
+		#[pallet::weight(T::WeightInfo::lend_kitty())]
+		pub fn lend_kitty(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			borrower: T::AccountId,
+			until_block: T::BlockNumber,
+			fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(borrower != who, Error::<T>::LendToSelf);
+			ensure!(
+				until_block > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidLeaseExpiry
+			);
+			Self::ensure_transferable(&bounded_dna)?;
+
+			let lease = Lease { borrower: borrower.clone(), until: until_block, fee };
+			<LeaseOffers<T>>::insert(&bounded_dna, lease);
+
+			Self::deposit_event(Event::LeaseOffered(dna, who, borrower, until_block, fee));
+			Ok(())
+		}
+
+		/// Accept a lease offered to the caller with `lend_kitty`, paying its fee to the owner.
+		#[pallet::weight(T::WeightInfo::accept_lease())]
+		#[transactional]
+		pub fn accept_lease(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let lease = <LeaseOffers<T>>::take(&bounded_dna).ok_or(Error::<T>::LeaseNotExist)?;
+			ensure!(lease.borrower == who, Error::<T>::NotLeaseBorrower);
+			ensure!(
+				lease.until > <frame_system::Pallet<T>>::block_number(),
+				Error::<T>::InvalidLeaseExpiry
+			);
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			Self::ensure_transferable(&bounded_dna)?;
+
+			<LeasesExpiring<T>>::try_mutate(lease.until, |expiring| {
+				expiring.try_push(bounded_dna.clone())
+			})
+			.map_err(|_| Error::<T>::TooManyLeasesExpiring)?;
+			T::Currency::transfer(&who, &kitty.owner, lease.fee, ExistenceRequirement::KeepAlive)?;
+			<KittiesForSale<T>>::remove(&bounded_dna);
+			let until = lease.until;
+			<Leases<T>>::insert(&bounded_dna, lease);
+
+			Self::deposit_event(Event::KittyLeased(dna, kitty.owner, who, until));
+			Ok(())
+		}
+
+		/// Withdraw a lease offer made by the caller. A running lease cannot be cancelled.
+		#[pallet::weight(T::WeightInfo::cancel_lease())]
+		pub fn cancel_lease(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			let lease = <LeaseOffers<T>>::take(&bounded_dna).ok_or(Error::<T>::LeaseNotExist)?;
+
+			Self::deposit_event(Event::LeaseCancelled(dna, who, lease.borrower));
+			Ok(())
+		}
+
+		/// Set the name of a kitty owned by the caller.
+		///
+		/// The deposit for the name and URI is reserved from the caller, and the previous deposit
+		/// released.
+		#[pallet::weight(T::WeightInfo::set_kitty_name())]
+		pub fn set_kitty_name(origin: OriginFor<T>, dna: Vec<u8>, name: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_metadata_unfrozen(&kitty)?;
+			let bounded_name =
+				KittyName::<T>::try_from(name.clone()).map_err(|_| Error::<T>::NameTooLong)?;
+
+			let uri = <Metadata<T>>::get(&bounded_dna).map(|metadata| metadata.uri);
+			Self::put_metadata(&who, &bounded_dna, bounded_name, uri.unwrap_or_default())?;
+
+			Self::deposit_event(Event::KittyNamed(dna, name));
+			Ok(())
+		}
+
+		/// Set the URI of the off-chain metadata, such as an image, of a kitty owned by the
+		/// caller.
+		///
+		/// The deposit for the name and URI is reserved from the caller, and the previous deposit
+		/// released.
+		#[pallet::weight(T::WeightInfo::set_metadata())]
+		pub fn set_metadata(origin: OriginFor<T>, dna: Vec<u8>, uri: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_metadata_unfrozen(&kitty)?;
+			let bounded_uri =
+				KittyUri::<T>::try_from(uri.clone()).map_err(|_| Error::<T>::UriTooLong)?;
+
+			let name = <Metadata<T>>::get(&bounded_dna).map(|metadata| metadata.name);
+			Self::put_metadata(&who, &bounded_dna, name.unwrap_or_default(), bounded_uri)?;
+
+			Self::deposit_event(Event::MetadataSet(dna, uri));
+			Ok(())
+		}
+
+		/// Clear the name and URI of a kitty owned by the caller and release their deposit.
+		#[pallet::weight(T::WeightInfo::clear_metadata())]
+		pub fn clear_metadata(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_metadata_unfrozen(&kitty)?;
+			let metadata =
+				<Metadata<T>>::take(&bounded_dna).ok_or(Error::<T>::MetadataNotExist)?;
+			T::Currency::unreserve(&metadata.depositor, metadata.deposit);
+
+			Self::deposit_event(Event::MetadataCleared(dna));
+			Ok(())
+		}
+
+		/// Set the attribute `key` of a kitty owned by the caller to `value`.
+		///
+		/// A deposit for the attribute is reserved from the caller, and the deposit for its
+		/// previous value released.
+		#[pallet::weight(T::WeightInfo::set_attribute())]
+		pub fn set_attribute(
+			origin: OriginFor<T>,
+			dna: Vec<u8>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_metadata_unfrozen(&kitty)?;
+			let bounded_key = AttributeKey::<T>::try_from(key.clone())
+				.map_err(|_| Error::<T>::AttributeKeyTooLong)?;
+			let bounded_value = AttributeValue::<T>::try_from(value.clone())
+				.map_err(|_| Error::<T>::AttributeValueTooLong)?;
+
+			let old = <Attributes<T>>::get(&bounded_dna, &bounded_key);
+			let is_new = old.is_none();
+			if is_new {
+				ensure!(
+					<AttributeCount<T>>::get(&bounded_dna) < T::MaxAttributes::get(),
+					Error::<T>::TooManyAttributes
+				);
+			}
+			let deposit = T::AttributeDepositBase::get()
+				.saturating_add(Self::byte_deposit(key.len() + value.len()));
+			Self::replace_deposit(
+				&who,
+				old.map(|attribute| (attribute.depositor, attribute.deposit)),
+				deposit,
+			)?;
+
+			if is_new {
+				<AttributeCount<T>>::mutate(&bounded_dna, |count| *count += 1);
+			}
+			let attribute = KittyAttribute { value: bounded_value, depositor: who, deposit };
+			<Attributes<T>>::insert(&bounded_dna, &bounded_key, attribute);
+
+			Self::deposit_event(Event::AttributeSet(dna, key, value));
+			Ok(())
+		}
+
+		/// Clear the attribute `key` of a kitty owned by the caller and release its deposit.
+		#[pallet::weight(T::WeightInfo::clear_attribute())]
+		pub fn clear_attribute(origin: OriginFor<T>, dna: Vec<u8>, key: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			Self::ensure_metadata_unfrozen(&kitty)?;
+			let bounded_key = AttributeKey::<T>::try_from(key.clone())
+				.map_err(|_| Error::<T>::AttributeNotExist)?;
+			let attribute = <Attributes<T>>::take(&bounded_dna, &bounded_key)
+				.ok_or(Error::<T>::AttributeNotExist)?;
+			<AttributeCount<T>>::mutate(&bounded_dna, |count| *count = count.saturating_sub(1));
+			T::Currency::unreserve(&attribute.depositor, attribute.deposit);
+
+			Self::deposit_event(Event::AttributeCleared(dna, key));
+			Ok(())
+		}
+
+		/// Create a collection owned by the caller with the given mint settings.
+		///
+		/// Kitties are added to it with `mint_into_collection`. `CollectionDeposit` is reserved
+		/// from the caller until the collection is destroyed with `destroy_collection`.
+		#[pallet::weight(T::WeightInfo::create_collection())]
+		pub fn create_collection(
+			origin: OriginFor<T>,
+			max_supply: Option<u32>,
+			mint_price: BalanceOf<T>,
+			mint_policy: MintPolicy,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let id = <NextCollectionId<T>>::get();
+			let next_id = id.checked_add(1).ok_or(Error::<T>::NoAvailableCollectionId)?;
+			let deposit = T::CollectionDeposit::get();
+			T::Currency::reserve(&who, deposit)?;
+			<CollectionDeposits<T>>::insert(id, (who.clone(), deposit));
+
+			let collection = Collection {
+				owner: who.clone(),
+				max_supply,
+				minted: 0,
+				mint_price,
+				mint_policy,
+				metadata_frozen: false,
+			};
+			<Collections<T>>::insert(id, collection);
+			<NextCollectionId<T>>::put(next_id);
+
+			Self::deposit_event(Event::CollectionCreated(id, who));
+			Ok(())
+		}
+
+		/// Destroy a collection owned by the caller and return its deposit.
+		///
+		/// Every kitty minted into the collection must have been burned first. The collection's
+		/// id is not reused, so its admin and allow-list entries are left behind.
+		#[pallet::weight(T::WeightInfo::destroy_collection())]
+		pub fn destroy_collection(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let details = <Collections<T>>::get(collection).ok_or(Error::<T>::CollectionNotExist)?;
+			ensure!(details.owner == who, Error::<T>::NotCollectionOwner);
+			ensure!(<CollectionSupply<T>>::get(collection) == 0, Error::<T>::CollectionNotEmpty);
+
+			<Collections<T>>::remove(collection);
+			<CollectionSupply<T>>::remove(collection);
+			if let Some((depositor, deposit)) = <CollectionDeposits<T>>::take(collection) {
+				T::Currency::unreserve(&depositor, deposit);
+			}
+
+			Self::deposit_event(Event::CollectionDestroyed(collection, who));
+			Ok(())
+		}
+
+		/// Change the max supply, mint price and mint policy of a collection.
+		///
+		/// The collection owner or one of its admins may call this. The max supply cannot go
+		/// below the number of kitties already minted.
+		#[pallet::weight(T::WeightInfo::set_collection_mint())]
+		pub fn set_collection_mint(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			max_supply: Option<u32>,
+			mint_price: BalanceOf<T>,
+			mint_policy: MintPolicy,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut details =
+				<Collections<T>>::get(collection).ok_or(Error::<T>::CollectionNotExist)?;
+			ensure!(
+				Self::is_collection_admin(collection, &details, &who),
+				Error::<T>::NotCollectionAdmin
+			);
+			ensure!(
+				max_supply.map_or(true, |max| max >= details.minted),
+				Error::<T>::MaxSupplyTooLow
+			);
+
+			details.max_supply = max_supply;
+			details.mint_price = mint_price;
+			details.mint_policy = mint_policy;
+			<Collections<T>>::insert(collection, details);
+
+			Self::deposit_event(Event::CollectionMintSet(
+				collection,
+				max_supply,
+				mint_price,
+				mint_policy,
+			));
+			Ok(())
+		}
+
+		/// Make `who` an admin of a collection owned by the caller, or stop it being one.
+		///
+		/// Admins may change the mint settings and the allow-list, and mint under any policy
+		/// without paying the mint price.
+		#[pallet::weight(T::WeightInfo::set_collection_admin())]
+		pub fn set_collection_admin(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			who: T::AccountId,
+			is_admin: bool,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let details = <Collections<T>>::get(collection).ok_or(Error::<T>::CollectionNotExist)?;
+			ensure!(details.owner == owner, Error::<T>::NotCollectionOwner);
+
+			if is_admin {
+				<CollectionAdmins<T>>::insert(collection, &who, ());
+			} else {
+				<CollectionAdmins<T>>::remove(collection, &who);
+			}
+
+			Self::deposit_event(Event::CollectionAdminSet(collection, who, is_admin));
+			Ok(())
+		}
+
+		/// Add `who` to the allow-list of a collection, or remove it.
+		///
+		/// The collection owner or one of its admins may call this. The allow-list only matters
+		/// under `MintPolicy::AllowList`.
+		#[pallet::weight(T::WeightInfo::set_allow_list())]
+		pub fn set_allow_list(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			who: T::AccountId,
+			allowed: bool,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			let details = <Collections<T>>::get(collection).ok_or(Error::<T>::CollectionNotExist)?;
+			ensure!(
+				Self::is_collection_admin(collection, &details, &caller),
+				Error::<T>::NotCollectionAdmin
+			);
+
+			if allowed {
+				<CollectionAllowList<T>>::insert(collection, &who, ());
+			} else {
+				<CollectionAllowList<T>>::remove(collection, &who);
+			}
+
+			Self::deposit_event(Event::AllowListSet(collection, who, allowed));
+			Ok(())
+		}
+
+		/// Freeze the names, URIs and attributes of every kitty in a collection owned by the
+		/// caller. This cannot be undone.
+		///
+		/// Burning a kitty of the collection still releases its metadata deposits.
+		#[pallet::weight(T::WeightInfo::freeze_collection_metadata())]
+		pub fn freeze_collection_metadata(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut details =
+				<Collections<T>>::get(collection).ok_or(Error::<T>::CollectionNotExist)?;
+			ensure!(details.owner == who, Error::<T>::NotCollectionOwner);
+
+			details.metadata_frozen = true;
+			<Collections<T>>::insert(collection, details);
+
+			Self::deposit_event(Event::CollectionMetadataFrozen(collection));
+			Ok(())
+		}
+
+		/// Mint a kitty into a collection, if the collection's mint policy allows the caller and
+		/// its max supply has not been reached.
+		///
+		/// Callers other than the collection owner and its admins pay the mint price to the
+		/// owner. The kitty starts without a price; use `set_price` before listing it. The caller
+		/// is recorded as its creator and receives `royalty` of every later sale, up to
+		/// `MaxRoyalty`.
+		///
+		/// The DNA must have been committed to with `commit_kitty`, as for `create_kitty`.
+		#[pallet::weight(T::WeightInfo::mint_into_collection(dna.len() as u32))]
+		#[transactional]
+		pub fn mint_into_collection(
+			origin: OriginFor<T>,
+			collection: CollectionId,
+			dna: Vec<u8>,
+			salt: [u8; 32],
+			royalty: Permill,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			ensure!(royalty <= T::MaxRoyalty::get(), Error::<T>::RoyaltyTooHigh);
+			let mut details =
+				<Collections<T>>::get(collection).ok_or(Error::<T>::CollectionNotExist)?;
+			let is_admin = Self::is_collection_admin(collection, &details, &who);
+			let allowed = match details.mint_policy {
+				MintPolicy::OwnerOnly => is_admin,
+				MintPolicy::AllowList =>
+					is_admin || <CollectionAllowList<T>>::contains_key(collection, &who),
+				MintPolicy::Public => true,
+			};
+			ensure!(allowed, Error::<T>::MintNotAllowed);
+			ensure!(
+				details.max_supply.map_or(true, |max| details.minted < max),
+				Error::<T>::CollectionFull
+			);
+			details.minted = details.minted.checked_add(1).ok_or(Error::<T>::CollectionFull)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			if !Self::take_commitment(&who, &dna, &bounded_dna, &salt)? {
+				return Ok(())
+			}
+
+			if !is_admin && !details.mint_price.is_zero() {
+				T::Currency::transfer(
+					&who,
+					&details.owner,
+					details.mint_price,
+					ExistenceRequirement::KeepAlive,
+				)?;
+			}
+
+			Self::mint(
+				&who,
+				bounded_dna.clone(),
+				Zero::zero(),
+				None,
+				0,
+				royalty,
+				Some(collection),
+			)?;
+			Self::reserve_deposit(&who, &bounded_dna)?;
+			<Collections<T>>::insert(collection, details);
+			<CollectionSupply<T>>::mutate(collection, |supply| *supply += 1);
+
+			Self::deposit_event(Event::KittyMinted(dna, collection, who));
+			Ok(())
+		}
+
+		/// Lock a kitty owned by the caller, so that it cannot be transferred, sold, auctioned,
+		/// lent or burned until the caller unlocks it.
+		///
+		/// The kitty is taken off the market. It cannot be locked while it is being auctioned.
+		#[pallet::weight(T::WeightInfo::lock_kitty())]
+		pub fn lock_kitty(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(!<Auctions<T>>::contains_key(&bounded_dna), Error::<T>::KittyInAuction);
+
+			<LockedKitties<T>>::insert(&bounded_dna, ());
+			<KittiesForSale<T>>::remove(&bounded_dna);
+
+			Self::deposit_event(Event::KittyLocked(dna, who));
+			Ok(())
+		}
+
+		/// Unlock a kitty owned by the caller. A kitty frozen by root stays frozen.
+		#[pallet::weight(T::WeightInfo::unlock_kitty())]
+		pub fn unlock_kitty(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			let kitty = <Kitties<T>>::get(&bounded_dna).ok_or(Error::<T>::KittyNotExist)?;
+			ensure!(kitty.owner == who, Error::<T>::KittyNotOwned);
+			ensure!(<LockedKitties<T>>::contains_key(&bounded_dna), Error::<T>::KittyNotLocked);
+
+			<LockedKitties<T>>::remove(&bounded_dna);
+
+			Self::deposit_event(Event::KittyUnlocked(dna, who));
+			Ok(())
+		}
+
+		/// Freeze any kitty, so that it cannot be transferred, sold, auctioned, lent or burned by
+		/// its owner until it is thawed, for example while its ownership is disputed.
+		///
+		/// The kitty is taken off the market, and a running auction for it is cancelled and its
+		/// best bid refunded.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::freeze_kitty())]
+		pub fn freeze_kitty(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			ensure!(<Kitties<T>>::contains_key(&bounded_dna), Error::<T>::KittyNotExist);
+			Self::cancel_auction(&bounded_dna);
+
+			<FrozenKitties<T>>::insert(&bounded_dna, ());
+			<KittiesForSale<T>>::remove(&bounded_dna);
+
+			Self::deposit_event(Event::KittyFrozen(dna));
+			Ok(())
+		}
+
+		/// Thaw a kitty frozen with `freeze_kitty`. A lock set by its owner stays in place.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::weight(T::WeightInfo::thaw_kitty())]
+		pub fn thaw_kitty(origin: OriginFor<T>, dna: Vec<u8>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let bounded_dna = Self::to_dna(dna.clone())?;
+			ensure!(<FrozenKitties<T>>::contains_key(&bounded_dna), Error::<T>::KittyNotFrozen);
+
+			<FrozenKitties<T>>::remove(&bounded_dna);
+
+			Self::deposit_event(Event::KittyThawed(dna));
+			Ok(())
+		}
+	}
+}
+
+// helper functions
+impl<T: Config> Pallet<T> {
+	fn to_dna(dna: Vec<u8>) -> Result<Dna<T>, Error<T>> {
+		Dna::<T>::try_from(dna).map_err(|_| Error::<T>::DnaTooLong)
+	}
+
+	// Draw a fresh random hash from the runtime's randomness source, the caller and a nonce.
+	fn random_hash(who: &T::AccountId) -> T::Hash {
+		let nonce = <Nonce<T>>::mutate(|nonce| {
+			*nonce = nonce.wrapping_add(1);
+			*nonce
+		});
+		let (seed, _) = T::KittyRandomness::random(&b"kitties/dna"[..]);
+		T::Hashing::hash_of(&(seed, who, nonce))
+	}
+
+	// Mix the parents' DNA. Each bit comes from `dna_a` where the random selector has a 1 and
+	// from `dna_b` where it has a 0; the child takes the length of a randomly chosen parent.
+	fn gen_child_dna(who: &T::AccountId, dna_a: &[u8], dna_b: &[u8]) -> Vec<u8> {
+		let seed = Self::random_hash(who);
+		let selector = seed.as_ref();
+
+		let len = if selector[0] % 2 == 0 { dna_a.len() } else { dna_b.len() };
+		(0..len)
+			.map(|i| {
+				let s = selector[i % selector.len()];
+				let a = dna_a.get(i).or_else(|| dna_b.get(i)).copied().unwrap_or_default();
+				let b = dna_b.get(i).or_else(|| dna_a.get(i)).copied().unwrap_or_default();
+				(a & s) | (b & !s)
+			})
+			.collect()
+	}
+
+	// Store a new kitty for `owner`, who becomes its creator, and add it to the owner index. The
+	// kitty is born in the current block and can breed straight away.
+	fn mint(
+		owner: &T::AccountId,
+		dna: Dna<T>,
+		price: BalanceOf<T>,
+		parents: Option<(Dna<T>, Dna<T>)>,
+		generation: u32,
+		royalty: Permill,
+		collection: Option<CollectionId>,
+	) -> DispatchResult {
+		ensure!(!<Kitties<T>>::contains_key(&dna), Error::<T>::KittyAlreadyExist);
+
+		Self::index_kitty(owner, &dna)?;
+
+		let genome = T::GenomeDecoder::decode(&dna);
+		let now = <frame_system::Pallet<T>>::block_number();
+		let mint_nonce = <NextMintNonce<T>>::mutate(|nonce| {
+			let current = *nonce;
+			*nonce = nonce.wrapping_add(1);
+			current
+		});
+		let kitty = Kitty {
+			dna: dna.clone(),
+			gender: genome.gender,
+			price,
+			owner: owner.clone(),
+			parents,
+			generation,
+			creator: owner.clone(),
+			royalty,
+			collection,
+			birth_block: now,
+			cooldown_until: now,
+			mint_nonce,
+		};
+
+		<Kitties<T>>::insert(&dna, kitty);
+
+		let mut current_number_kitties = <NumOfKitties<T>>::get();
+		current_number_kitties += 1;
+		NumOfKitties::<T>::put(current_number_kitties);
+
+		Self::deposit_event(Event::KittyGenome(dna.into_inner(), genome));
+
+		Ok(())
+	}
+
+	// The number of blocks a kitty of `generation` rests after breeding.
+	fn cooldown_of(generation: u32) -> T::BlockNumber {
+		let schedule = T::CooldownSchedule::get();
+		schedule
+			.get(generation as usize)
+			.or_else(|| schedule.last())
+			.copied()
+			.unwrap_or_else(Zero::zero)
+	}
+
+	fn is_approved_for_all(owner: &T::AccountId, operator: &T::AccountId) -> bool {
+		<OperatorApprovals<T>>::contains_key(owner, operator)
+	}
+
+	// Whether `who` may move `kitty`: its owner, the account approved for it, or an operator of
+	// its owner.
+	fn is_approved_or_owner(dna: &Dna<T>, kitty: &Kitty<T>, who: &T::AccountId) -> bool {
+		kitty.owner == *who ||
+			<KittyApprovals<T>>::get(dna).as_ref() == Some(who) ||
+			Self::is_approved_for_all(&kitty.owner, who)
+	}
+
+	// Fail if the kitty is locked by its owner or frozen by root.
+	fn ensure_not_frozen(dna: &Dna<T>) -> DispatchResult {
+		ensure!(
+			!<LockedKitties<T>>::contains_key(dna) && !<FrozenKitties<T>>::contains_key(dna),
+			Error::<T>::Frozen
+		);
+		Ok(())
+	}
+
+	// Fail if the kitty is locked in a way that forbids changing its owner.
+	fn ensure_transferable(dna: &Dna<T>) -> DispatchResult {
+		Self::ensure_not_frozen(dna)?;
+		ensure!(!<Auctions<T>>::contains_key(dna), Error::<T>::KittyInAuction);
+		ensure!(!<PendingTransfers<T>>::contains_key(dna), Error::<T>::TransferPending);
+		ensure!(!<Leases<T>>::contains_key(dna), Error::<T>::KittyLeased);
+		Ok(())
+	}
+
+	// Fail if the kitty belongs to a collection whose metadata is frozen.
+	fn ensure_metadata_unfrozen(kitty: &Kitty<T>) -> DispatchResult {
+		if let Some(id) = kitty.collection {
+			let frozen = <Collections<T>>::get(id).map_or(false, |c| c.metadata_frozen);
+			ensure!(!frozen, Error::<T>::MetadataFrozen);
+		}
+		Ok(())
+	}
+
+	// Whether `who` owns the collection `id` or is one of its admins.
+	fn is_collection_admin(
+		id: CollectionId,
+		collection: &Collection<T>,
+		who: &T::AccountId,
+	) -> bool {
+		collection.owner == *who || <CollectionAdmins<T>>::contains_key(id, who)
+	}
+
+	// Close the auction for `dna`. The best bid buys the kitty; if there is none, the winner
+	// cannot take another kitty or the bid is no longer fully reserved, the auction is cancelled
+	// and whatever is left of the bid refunded.
+	fn settle_auction(dna: Dna<T>) {
+		let auction = match <Auctions<T>>::take(&dna) {
+			Some(auction) => auction,
+			None => return,
+		};
+
+		if let Some((winner, amount)) = auction.best_bid {
+			if let Some(kitty) = <Kitties<T>>::get(&dna) {
+				if Self::sell_reserved(&dna, kitty, &winner, &auction.seller, amount).is_ok() {
+					Self::deposit_event(Event::AuctionSettled(
+						dna.into_inner(),
+						auction.seller,
+						winner,
+						amount,
+					));
+					return
+				}
+			}
+			T::Currency::unreserve(&winner, amount);
+		}
+
+		Self::deposit_event(Event::AuctionCancelled(dna.into_inner()));
+	}
+
+	// The part of `price` owed to the creator of `kitty` when `seller` sells it. Creators owe
+	// themselves nothing, and a royalty too small to keep the creator's account alive is left
+	// with the seller rather than blocking the sale.
+	fn royalty_of(kitty: &Kitty<T>, seller: &T::AccountId, price: BalanceOf<T>) -> BalanceOf<T> {
+		let royalty = kitty.royalty.mul_floor(price);
+		let creator_balance = T::Currency::total_balance(&kitty.creator);
+		if kitty.creator == *seller ||
+			creator_balance.saturating_add(royalty) < T::Currency::minimum_balance()
+		{
+			return Zero::zero()
+		}
+		royalty
+	}
+
+	// Pay `price` for `kitty` from the free balance of `buyer`: the royalty to its creator and
+	// the rest to `seller`.
+	fn pay_sale(
+		dna: &Dna<T>,
+		kitty: &Kitty<T>,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		price: BalanceOf<T>,
+	) -> DispatchResult {
+		let royalty = Self::royalty_of(kitty, seller, price);
+		if !royalty.is_zero() {
+			T::Currency::transfer(buyer, &kitty.creator, royalty, ExistenceRequirement::KeepAlive)?;
+			Self::deposit_event(Event::RoyaltyPaid(dna.to_vec(), kitty.creator.clone(), royalty));
+		}
+		T::Currency::transfer(buyer, seller, price - royalty, ExistenceRequirement::KeepAlive)
+	}
+
+	// Like `pay_sale`, for an `amount` already reserved from `buyer`. Fails if less than `amount`
+	// is still reserved, so callers must roll back on error.
+	fn pay_sale_reserved(
+		dna: &Dna<T>,
+		kitty: &Kitty<T>,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		let royalty = Self::royalty_of(kitty, seller, amount);
+		if !royalty.is_zero() {
+			let unpaid = T::Currency::repatriate_reserved(
+				buyer,
+				&kitty.creator,
+				royalty,
+				BalanceStatus::Free,
+			)?;
+			ensure!(unpaid.is_zero(), Error::<T>::PaymentNotReserved);
+			Self::deposit_event(Event::RoyaltyPaid(dna.to_vec(), kitty.creator.clone(), royalty));
+		}
+		let unpaid =
+			T::Currency::repatriate_reserved(buyer, seller, amount - royalty, BalanceStatus::Free)?;
+		ensure!(unpaid.is_zero(), Error::<T>::PaymentNotReserved);
+		Ok(())
+	}
+
+	// Hand the kitty to `buyer` and pay `seller` the `amount` reserved from `buyer`, or do
+	// neither.
+	#[transactional]
+	fn sell_reserved(
+		dna: &Dna<T>,
+		kitty: Kitty<T>,
+		buyer: &T::AccountId,
+		seller: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		Self::transfer_kitty(dna, kitty.clone(), buyer)?;
+		Self::pay_sale_reserved(dna, &kitty, buyer, seller, amount)
+	}
+
+	// Drop an unrevealed commitment and slash its deposit.
+	fn expire_commitment(who: T::AccountId, hash: T::Hash) {
+		if let Some(commitment) = <Commitments<T>>::take(&who, &hash) {
+			let (_, unslashed) = T::Currency::slash_reserved(&who, commitment.deposit);
+			let slashed = commitment.deposit.saturating_sub(unslashed);
+			Self::deposit_event(Event::CommitmentExpired(who, hash, slashed));
+		}
+	}
+
+	// Use up the commitment of `who` to `dna` and `salt` and return its deposit. If someone else
+	// minted the DNA in the meantime, `false` is returned so the caller mints nothing; failing
+	// instead would keep the commitment until its deposit is slashed.
+	fn take_commitment(
+		who: &T::AccountId,
+		dna: &[u8],
+		bounded_dna: &Dna<T>,
+		salt: &[u8; 32],
+	) -> Result<bool, DispatchError> {
+		let hash = T::Hashing::hash_of(&(who, dna, salt));
+		let commitment =
+			<Commitments<T>>::take(who, &hash).ok_or(Error::<T>::CommitmentNotExist)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(commitment.committed_at < now, Error::<T>::RevealTooEarly);
+
+		<CommitmentsExpiring<T>>::mutate(commitment.expiry, |expiring| {
+			expiring.retain(|(x, h)| x != who || *h != hash)
+		});
+		T::Currency::unreserve(who, commitment.deposit);
+
+		if <Kitties<T>>::contains_key(bounded_dna) {
+			Self::deposit_event(Event::CommitmentRefunded(who.clone(), hash, commitment.deposit));
+			return Ok(false)
+		}
+		Ok(true)
+	}
+
+	// Drop an offer that reached its expiry and refund it.
+	fn expire_offer(dna: Dna<T>, bidder: T::AccountId) {
+		if let Some(offer) = <Offers<T>>::take(&dna, &bidder) {
+			T::Currency::unreserve(&bidder, offer.amount);
+			Self::deposit_event(Event::OfferExpired(dna.into_inner(), bidder));
+		}
+	}
+
+	// Take an offer that ends early out of the list of offers expiring at `expiry`.
+	fn remove_offer_expiry(dna: &Dna<T>, bidder: &T::AccountId, expiry: T::BlockNumber) {
+		<OffersExpiring<T>>::mutate(expiry, |expiring| {
+			expiring.retain(|(d, b)| d != dna || b != bidder)
+		});
+	}
+
+	// Add the kitty to the owner index of `owner`, unless the owner is already at
+	// `MaxKittiesOwned`.
+	fn index_kitty(owner: &T::AccountId, dna: &Dna<T>) -> DispatchResult {
+		let count = <KittiesOwnedCount<T>>::get(owner);
+		ensure!(count < T::MaxKittiesOwned::get(), Error::<T>::TooManyKittiesOwned);
+
+		<KittiesOwned<T>>::insert(owner, dna, ());
+		<KittiesOwnedCount<T>>::insert(owner, count + 1);
+		Ok(())
+	}
+
+	// Remove the kitty from the owner index of `owner`.
+	fn unindex_kitty(owner: &T::AccountId, dna: &Dna<T>) -> DispatchResult {
+		ensure!(<KittiesOwned<T>>::contains_key(owner, dna), Error::<T>::KittyNotOwned);
+
+		<KittiesOwned<T>>::remove(owner, dna);
+		let count = <KittiesOwnedCount<T>>::get(owner).saturating_sub(1);
+		if count == 0 {
+			<KittiesOwnedCount<T>>::remove(owner);
+		} else {
+			<KittiesOwnedCount<T>>::insert(owner, count);
+		}
+		Ok(())
+	}
+
+	// Reserve the creation deposit from `who` for the kitty it just minted.
+	fn reserve_deposit(who: &T::AccountId, dna: &Dna<T>) -> DispatchResult {
+		let deposit = T::KittyDeposit::get();
+		if !deposit.is_zero() {
+			T::Currency::reserve(who, deposit)?;
+			<KittyDeposits<T>>::insert(dna, (who.clone(), deposit));
+		}
+		Ok(())
+	}
+
+	// Remove `kitty` together with its index, listing, approval, lease, lock and metadata entries,
+	// and refund its creation and metadata deposits.
+	fn burn(dna: &Dna<T>, kitty: Kitty<T>) -> DispatchResult {
+		Self::unindex_kitty(&kitty.owner, dna)?;
+
+		<Kitties<T>>::remove(dna);
+		<KittiesForSale<T>>::remove(dna);
+		<KittyApprovals<T>>::remove(dna);
+		<PendingTransfers<T>>::remove(dna);
+		<LeaseOffers<T>>::remove(dna);
+		<LockedKitties<T>>::remove(dna);
+		<FrozenKitties<T>>::remove(dna);
+		if let Some(lease) = <Leases<T>>::take(dna) {
+			<LeasesExpiring<T>>::mutate(lease.until, |expiring| expiring.retain(|x| x != dna));
+		}
+		<NumOfKitties<T>>::mutate(|count| *count = count.saturating_sub(1));
+		if let Some(id) = kitty.collection {
+			<CollectionSupply<T>>::mutate(id, |supply| *supply = supply.saturating_sub(1));
+		}
+
+		if let Some((depositor, deposit)) = <KittyDeposits<T>>::take(dna) {
+			T::Currency::unreserve(&depositor, deposit);
+		}
+		if let Some(metadata) = <Metadata<T>>::take(dna) {
+			T::Currency::unreserve(&metadata.depositor, metadata.deposit);
+		}
+		// At most `MaxAttributes` entries.
+		for (_, attribute) in <Attributes<T>>::drain_prefix(dna) {
+			T::Currency::unreserve(&attribute.depositor, attribute.deposit);
+		}
+		<AttributeCount<T>>::remove(dna);
+
+		Self::deposit_event(Event::KittyBurned(dna.to_vec(), kitty.owner));
+		Ok(())
+	}
+
+	// The deposit for `len` bytes of metadata, on top of the base deposit.
+	fn byte_deposit(len: usize) -> BalanceOf<T> {
+		T::DepositPerByte::get().saturating_mul((len as u32).into())
+	}
+
+	// Reserve `amount` from `who` for an item whose current deposit, if any, is `old`, and release
+	// `old`. Only the difference moves when `who` already holds the deposit.
+	fn replace_deposit(
+		who: &T::AccountId,
+		old: Option<(T::AccountId, BalanceOf<T>)>,
+		amount: BalanceOf<T>,
+	) -> DispatchResult {
+		match old {
+			Some((depositor, deposit)) if depositor == *who => {
+				if amount > deposit {
+					T::Currency::reserve(who, amount - deposit)?;
+				} else {
+					T::Currency::unreserve(who, deposit - amount);
+				}
+			},
+			old => {
+				T::Currency::reserve(who, amount)?;
+				if let Some((depositor, deposit)) = old {
+					T::Currency::unreserve(&depositor, deposit);
+				}
+			},
+		}
+		Ok(())
+	}
+
+	// Store `name` and `uri` as the metadata of `dna`, with their deposit reserved from `who`.
+	fn put_metadata(
+		who: &T::AccountId,
+		dna: &Dna<T>,
+		name: KittyName<T>,
+		uri: KittyUri<T>,
+	) -> DispatchResult {
+		let deposit = T::MetadataDepositBase::get()
+			.saturating_add(Self::byte_deposit(name.len() + uri.len()));
+		let old = <Metadata<T>>::get(dna).map(|metadata| (metadata.depositor, metadata.deposit));
+		Self::replace_deposit(who, old, deposit)?;
+
+		<Metadata<T>>::insert(dna, KittyMetadata { name, uri, depositor: who.clone(), deposit });
+		Ok(())
+	}
+
+	// End the running lease of `dna`, if any.
+	fn end_lease(dna: Dna<T>) {
+		if let Some(lease) = <Leases<T>>::take(&dna) {
+			Self::deposit_event(Event::LeaseEnded(dna.into_inner(), lease.borrower));
+		}
+	}
+
+	// Drop the auction for `dna`, if any, and refund its best bid.
+	fn cancel_auction(dna: &Dna<T>) {
+		if let Some(auction) = <Auctions<T>>::take(dna) {
+			<AuctionsEnding<T>>::mutate(auction.end, |ending| ending.retain(|x| x != dna));
+			if let Some((bidder, amount)) = auction.best_bid {
+				T::Currency::unreserve(&bidder, amount);
+			}
+			Self::deposit_event(Event::AuctionCancelled(dna.to_vec()));
+		}
+	}
+
+	// Move `kitty` from its current owner to `new_owner`, keeping the owner index in sync, taking
+	// the kitty off the market and clearing its approval, transfer offer and lease offer.
+	//
+	// Every path that changes a kitty's owner goes through here, so locked and frozen kitties are
+	// refused here too.
+	fn transfer_kitty(dna: &Dna<T>, mut kitty: Kitty<T>, new_owner: &T::AccountId) -> DispatchResult {
+		Self::ensure_not_frozen(dna)?;
+		if kitty.owner != *new_owner {
+			// A kitty missing from its owner's index means the index has drifted; refuse the
+			// transfer rather than make it worse.
+			ensure!(<KittiesOwned<T>>::contains_key(&kitty.owner, dna), Error::<T>::KittyNotOwned);
+			Self::index_kitty(new_owner, dna)?;
+			Self::unindex_kitty(&kitty.owner, dna)?;
+		}
+
+		<KittiesForSale<T>>::remove(dna);
+		<KittyApprovals<T>>::remove(dna);
+		<PendingTransfers<T>>::remove(dna);
+		<LeaseOffers<T>>::remove(dna);
+
+		kitty.owner = new_owner.clone();
+		<Kitties<T>>::insert(dna, kitty);
+
+		Ok(())
+	}
+}
+
+// runtime api helpers
+impl<T: Config> Pallet<T> {
+	/// The kitty with the given DNA, if it exists.
+	pub fn kitty_by_dna(dna: Vec<u8>) -> Option<Kitty<T>> {
+		<Kitties<T>>::get(Self::to_dna(dna).ok()?)
+	}
+
+	/// The traits of the kitty with the given DNA, if it exists.
+	pub fn genome(dna: Vec<u8>) -> Option<Genome> {
+		let dna = Self::to_dna(dna).ok()?;
+		<Kitties<T>>::contains_key(&dna).then(|| T::GenomeDecoder::decode(&dna))
+	}
+
+	/// Up to `limit` DNAs of kitties owned by `owner`, in storage order.
+	///
+	/// The page starts after `start_after`; pass the last DNA of one page to get the next.
+	pub fn kitties_of(
+		owner: T::AccountId,
+		start_after: Option<Vec<u8>>,
+		limit: u32,
+	) -> Vec<Vec<u8>> {
+		let kitties = match start_after {
+			Some(dna) => match Self::to_dna(dna) {
+				Ok(dna) => <KittiesOwned<T>>::iter_key_prefix_from(
+					&owner,
+					<KittiesOwned<T>>::hashed_key_for(&owner, &dna),
+				),
+				Err(_) => return Vec::new(),
+			},
+			None => <KittiesOwned<T>>::iter_key_prefix(&owner),
+		};
+		kitties.take(limit as usize).map(|dna| dna.into_inner()).collect()
+	}
+
+	/// The account that may use the kitty with the given DNA: its borrower while it is leased,
+	/// otherwise its owner.
+	pub fn kitty_user(dna: Vec<u8>) -> Option<T::AccountId> {
+		let dna = Self::to_dna(dna).ok()?;
+		match <Leases<T>>::get(&dna) {
+			Some(lease) => Some(lease.borrower),
+			None => <Kitties<T>>::get(&dna).map(|kitty| kitty.owner),
+		}
+	}
+
+	/// The name, URI and attributes of the kitty with the given DNA, if it exists.
+	pub fn metadata(dna: Vec<u8>) -> Option<MetadataInfo> {
+		let dna = Self::to_dna(dna).ok()?;
+		if !<Kitties<T>>::contains_key(&dna) {
+			return None
+		}
+
+		let mut info = MetadataInfo::default();
+		if let Some(metadata) = <Metadata<T>>::get(&dna) {
+			info.name = metadata.name.into_inner();
+			info.uri = metadata.uri.into_inner();
+		}
+		info.attributes = <Attributes<T>>::iter_prefix(&dna)
+			.map(|(key, attribute)| (key.into_inner(), attribute.value.into_inner()))
+			.collect();
+		Some(info)
+	}
+
+	/// The DNA and price of every kitty listed for sale.
+	pub fn listed_kitties() -> Vec<(Vec<u8>, BalanceOf<T>)> {
+		<KittiesForSale<T>>::iter_keys()
+			.filter_map(|dna| <Kitties<T>>::get(&dna).map(|kitty| (dna.into_inner(), kitty.price)))
+			.collect()
 	}
 }
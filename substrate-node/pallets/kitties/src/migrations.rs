@@ -0,0 +1,780 @@
+//! Storage migrations for pallet_kitties.
+//!
+//! Each version has its own module with an `OnRuntimeUpgrade` that only runs when the on-chain
+//! storage version is the one right before it. Add them to the runtime's `Migrations` tuple in
+//! order.
+
+use super::*;
+use frame_support::{
+	log,
+	storage::migration::{put_storage_value, storage_iter, storage_key_iter},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	StorageHasher,
+};
+use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData};
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::OnRuntimeUpgradeHelpersExt;
+
+// Kitty as stored from v1 until v3, before creators and royalties were recorded. Migrations up to
+// v2 read and write this layout rather than `Kitty<T>`, so they keep working as `Kitty` grows.
+#[derive(Encode, Decode)]
+struct KittyV2<T: Config> {
+	dna: Dna<T>,
+	owner: T::AccountId,
+	price: BalanceOf<T>,
+	gender: Gender,
+	parents: Option<(Dna<T>, Dna<T>)>,
+	generation: u32,
+}
+
+// Kitty as stored in v3, before collections were recorded. `MigrateToV3` writes this layout
+// rather than `Kitty<T>` for the same reason.
+#[derive(Encode, Decode)]
+struct KittyV3<T: Config> {
+	dna: Dna<T>,
+	owner: T::AccountId,
+	price: BalanceOf<T>,
+	gender: Gender,
+	parents: Option<(Dna<T>, Dna<T>)>,
+	generation: u32,
+	creator: T::AccountId,
+	royalty: Permill,
+}
+
+// Kitty as stored in v4, before birth blocks and breeding cooldowns were recorded. `MigrateToV4`
+// writes this layout rather than `Kitty<T>` for the same reason.
+#[derive(Encode, Decode)]
+struct KittyV4<T: Config> {
+	dna: Dna<T>,
+	owner: T::AccountId,
+	price: BalanceOf<T>,
+	gender: Gender,
+	parents: Option<(Dna<T>, Dna<T>)>,
+	generation: u32,
+	creator: T::AccountId,
+	royalty: Permill,
+	collection: Option<CollectionId>,
+}
+
+// Kitty as stored from v5, after birth blocks and breeding cooldowns were recorded. `MigrateToV5`
+// writes this layout rather than `Kitty<T>` for the same reason, and later migrations read it.
+#[derive(Encode, Decode)]
+struct KittyV5<T: Config> {
+	dna: Dna<T>,
+	owner: T::AccountId,
+	price: BalanceOf<T>,
+	gender: Gender,
+	parents: Option<(Dna<T>, Dna<T>)>,
+	generation: u32,
+	creator: T::AccountId,
+	royalty: Permill,
+	collection: Option<CollectionId>,
+	birth_block: T::BlockNumber,
+	cooldown_until: T::BlockNumber,
+}
+
+// Kitty as stored from v8, after mint nonces were recorded. `MigrateToV8` writes this layout
+// rather than `Kitty<T>` for the same reason.
+#[derive(Encode, Decode)]
+struct KittyV8<T: Config> {
+	dna: Dna<T>,
+	owner: T::AccountId,
+	price: BalanceOf<T>,
+	gender: Gender,
+	parents: Option<(Dna<T>, Dna<T>)>,
+	generation: u32,
+	creator: T::AccountId,
+	royalty: Permill,
+	collection: Option<CollectionId>,
+	birth_block: T::BlockNumber,
+	cooldown_until: T::BlockNumber,
+	mint_nonce: u64,
+}
+
+// Offer as stored until v5, before the block it was made in was recorded.
+#[derive(Encode, Decode)]
+struct OfferV5<T: Config> {
+	amount: BalanceOf<T>,
+	expiry: T::BlockNumber,
+}
+
+// Offer as stored in v6 and v7, dated by the block it was made in.
+#[derive(Encode, Decode)]
+struct OfferV6<T: Config> {
+	amount: BalanceOf<T>,
+	expiry: T::BlockNumber,
+	made_at: T::BlockNumber,
+}
+
+// Offer as stored from v8, tied to the mint nonce of the kitty it was made for.
+#[derive(Encode, Decode)]
+struct OfferV8<T: Config> {
+	amount: BalanceOf<T>,
+	expiry: T::BlockNumber,
+	kitty_nonce: u64,
+}
+
+// Count kitties in any layout, by their keys alone. `pre_upgrade` hooks run before every
+// migration in the runtime's tuple, so they may see storage from any earlier version.
+#[cfg(feature = "try-runtime")]
+fn kitty_count<T: Config>() -> u32 {
+	let prefix = frame_support::storage::storage_prefix(Pallet::<T>::name().as_bytes(), b"Kitties");
+	frame_support::storage::KeyPrefixIterator::new(prefix.to_vec(), prefix.to_vec(), |_| Ok(()))
+		.count() as u32
+}
+
+// Iterate kitties in their v2 layout. A `Kitty` from a later version starts with the same
+// fields, so this also reads kitties that have already been migrated further.
+fn kitties_v2<T: Config>() -> impl Iterator<Item = (Dna<T>, KittyV2<T>)> {
+	storage_key_iter::<Dna<T>, KittyV2<T>, Blake2_128Concat>(
+		Pallet::<T>::name().as_bytes(),
+		b"Kitties",
+	)
+}
+
+/// Moves from the original layout, where kitties were keyed by raw DNA and priced in `u32`, to
+/// bounded DNA and `BalanceOf<T>` prices.
+pub mod v1 {
+	use super::*;
+
+	// Kitty as stored before v1.
+	#[derive(Decode)]
+	struct OldKitty<AccountId> {
+		dna: Vec<u8>,
+		owner: AccountId,
+		price: u32,
+		#[allow(dead_code)]
+		gender: Gender,
+	}
+
+	/// Rewrites every kitty and rebuilds the owner index from `Kitties`.
+	///
+	/// The owner index is written in its v1 layout, a `BoundedVec` of DNA per owner, which
+	/// `v2::MigrateToV2` then turns into the current double map.
+	///
+	/// Kitties whose DNA is longer than `MaxDnaLen` cannot be represented any more and are
+	/// dropped. Each one is logged with its owner, and `post_upgrade` fails if any was dropped,
+	/// so that a try-runtime check catches it before the upgrade is enacted. Owners keep every
+	/// kitty, even past `MaxKittiesOwned`, since the index has no hard cap from v2 on. Gender is
+	/// decoded again with `T::GenomeDecoder`, so it agrees with the genome the runtime API
+	/// reports.
+	pub struct MigrateToV1<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV1<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				log::info!(target: "runtime::kitties", "skipping v1 migration: already applied");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = Pallet::<T>::name().as_bytes();
+			let old_kitties: Vec<(Vec<u8>, OldKitty<T::AccountId>)> =
+				storage_key_iter::<Vec<u8>, OldKitty<T::AccountId>, Blake2_128Concat>(
+					pallet,
+					b"Kitties",
+				)
+				.drain()
+				.collect();
+			let old_owners = storage_key_iter::<T::AccountId, Vec<Vec<u8>>, Blake2_128Concat>(
+				pallet,
+				b"KittiesOwned",
+			)
+			.drain()
+			.count() as u64;
+
+			let total = old_kitties.len() as u64;
+			let mut migrated = 0u32;
+			let mut owners: BTreeMap<T::AccountId, Vec<Dna<T>>> = BTreeMap::new();
+			for (_, old) in old_kitties {
+				let dna = match Dna::<T>::try_from(old.dna.clone()) {
+					Ok(dna) => dna,
+					Err(_) => {
+						log::warn!(
+							target: "runtime::kitties",
+							"dropping kitty {:?} of {:?}: DNA longer than MaxDnaLen",
+							old.dna,
+							old.owner,
+						);
+						continue
+					},
+				};
+				owners.entry(old.owner.clone()).or_default().push(dna.clone());
+
+				let kitty = KittyV2::<T> {
+					dna: dna.clone(),
+					owner: old.owner,
+					price: old.price.into(),
+					gender: T::GenomeDecoder::decode(&dna).gender,
+					parents: None,
+					generation: 0,
+				};
+				let key = Blake2_128Concat::hash(&dna.encode());
+				put_storage_value(pallet, b"Kitties", &key, kitty);
+				migrated += 1;
+			}
+
+			let new_owners = owners.len() as u64;
+			for (owner, owned) in owners {
+				let key = Blake2_128Concat::hash(&owner.encode());
+				put_storage_value(pallet, b"KittiesOwned", &key, owned);
+			}
+
+			<NumOfKitties<T>>::put(migrated);
+			StorageVersion::new(1).put::<Pallet<T>>();
+
+			log::info!(
+				target: "runtime::kitties",
+				"migrated {} of {} kitties to v1, {} dropped",
+				migrated,
+				total,
+				total - migrated as u64,
+			);
+
+			// Each old kitty and owner entry is read and removed; each kept kitty is written once
+			// and each owner's index once.
+			T::DbWeight::get().reads_writes(
+				1 + total + old_owners,
+				2 + total + old_owners + migrated as u64 + new_owners,
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			// Nothing to check when this migration is kept around after it has been applied.
+			if Pallet::<T>::on_chain_storage_version() != 0 {
+				return Ok(())
+			}
+
+			let count = storage_key_iter::<Vec<u8>, OldKitty<T::AccountId>, Blake2_128Concat>(
+				Pallet::<T>::name().as_bytes(),
+				b"Kitties",
+			)
+			.count() as u32;
+			Self::set_temp_storage(count, "kitty_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 1, "expected storage version 1");
+
+			// The owner index is checked by later migrations and by the pallet's own
+			// `post_upgrade`, since it is no longer in its v1 layout once they have run.
+			if let Some(old_count) = Self::get_temp_storage::<u32>("kitty_count") {
+				let count = <Kitties<T>>::iter_keys().count() as u32;
+				ensure!(count == old_count, "migration dropped kitties");
+				ensure!(<NumOfKitties<T>>::get() == count, "NumOfKitties does not match Kitties");
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Moves the owner index from a `BoundedVec` of DNA per owner to a double map keyed by owner and
+/// DNA, with the number of kitties per owner kept in `KittiesOwnedCount`.
+pub mod v2 {
+	use super::*;
+
+	/// Drops the v1 owner index and rebuilds `KittiesOwned` and `KittiesOwnedCount` from
+	/// `Kitties`, which is the source of truth for ownership.
+	pub struct MigrateToV2<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV2<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 1 {
+				log::info!(target: "runtime::kitties", "skipping v2 migration: not on v1");
+				return T::DbWeight::get().reads(1)
+			}
+
+			// The old and the new index share a prefix, so the old entries have to be gone
+			// before anything is written.
+			let old_owners = storage_key_iter::<T::AccountId, Vec<Dna<T>>, Blake2_128Concat>(
+				Pallet::<T>::name().as_bytes(),
+				b"KittiesOwned",
+			)
+			.drain()
+			.count() as u64;
+
+			let mut indexed = 0u64;
+			for (dna, kitty) in kitties_v2::<T>() {
+				<KittiesOwned<T>>::insert(&kitty.owner, &dna, ());
+				<KittiesOwnedCount<T>>::mutate(&kitty.owner, |count| *count += 1);
+				indexed += 1;
+			}
+
+			StorageVersion::new(2).put::<Pallet<T>>();
+
+			log::info!(
+				target: "runtime::kitties",
+				"migrated owner index of {} kitties from {} owners to v2",
+				indexed,
+				old_owners,
+			);
+
+			// Each old owner entry is read and removed; each kitty is read and written to the
+			// index and to its owner's counter.
+			T::DbWeight::get().reads_writes(
+				1 + old_owners + 2 * indexed,
+				1 + old_owners + 2 * indexed,
+			)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() >= 2 {
+				return Ok(())
+			}
+
+			Self::set_temp_storage(kitty_count::<T>(), "v2_kitty_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// Later migrations in the same tuple have already run by now.
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 2, "expected storage version 2");
+
+			if let Some(count) = Self::get_temp_storage::<u32>("v2_kitty_count") {
+				ensure!(<NumOfKitties<T>>::get() == count, "migration changed the kitty count");
+			}
+			ensure!(
+				<KittiesOwned<T>>::iter_keys().count() == <Kitties<T>>::iter_keys().count(),
+				"owner index does not have one entry per kitty"
+			);
+			for (dna, kitty) in kitties_v2::<T>() {
+				ensure!(
+					<KittiesOwned<T>>::contains_key(&kitty.owner, &dna),
+					"kitty is missing from its owner's index"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Records a creator and a royalty on every kitty.
+pub mod v3 {
+	use super::*;
+
+	/// Rewrites every kitty with its current owner as creator and no royalty, since neither was
+	/// tracked before.
+	pub struct MigrateToV3<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV3<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 2 {
+				log::info!(target: "runtime::kitties", "skipping v3 migration: not on v2");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = Pallet::<T>::name().as_bytes();
+			let old_kitties: Vec<(Dna<T>, KittyV2<T>)> = kitties_v2::<T>().collect();
+			let migrated = old_kitties.len() as u64;
+			for (dna, old) in old_kitties {
+				let kitty = KittyV3::<T> {
+					dna: old.dna,
+					creator: old.owner.clone(),
+					owner: old.owner,
+					price: old.price,
+					gender: old.gender,
+					parents: old.parents,
+					generation: old.generation,
+					royalty: Permill::zero(),
+				};
+				let key = Blake2_128Concat::hash(&dna.encode());
+				put_storage_value(pallet, b"Kitties", &key, kitty);
+			}
+
+			StorageVersion::new(3).put::<Pallet<T>>();
+
+			log::info!(target: "runtime::kitties", "migrated {} kitties to v3", migrated);
+
+			T::DbWeight::get().reads_writes(1 + migrated, 1 + migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() >= 3 {
+				return Ok(())
+			}
+
+			Self::set_temp_storage(kitty_count::<T>(), "v3_kitty_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// Later migrations in the same tuple have already run by now.
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 3, "expected storage version 3");
+
+			if let Some(count) = Self::get_temp_storage::<u32>("v3_kitty_count") {
+				ensure!(kitties_v2::<T>().count() as u32 == count, "migration dropped kitties");
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Records the collection of every kitty.
+pub mod v4 {
+	use super::*;
+
+	/// Rewrites every kitty as belonging to no collection, since collections did not exist
+	/// before.
+	pub struct MigrateToV4<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV4<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 3 {
+				log::info!(target: "runtime::kitties", "skipping v4 migration: not on v3");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = Pallet::<T>::name().as_bytes();
+			let old_kitties: Vec<(Dna<T>, KittyV3<T>)> =
+				storage_key_iter::<Dna<T>, KittyV3<T>, Blake2_128Concat>(pallet, b"Kitties")
+					.collect();
+			let migrated = old_kitties.len() as u64;
+			for (dna, old) in old_kitties {
+				let kitty = KittyV4::<T> {
+					dna: old.dna,
+					owner: old.owner,
+					price: old.price,
+					gender: old.gender,
+					parents: old.parents,
+					generation: old.generation,
+					creator: old.creator,
+					royalty: old.royalty,
+					collection: None,
+				};
+				let key = Blake2_128Concat::hash(&dna.encode());
+				put_storage_value(pallet, b"Kitties", &key, kitty);
+			}
+
+			StorageVersion::new(4).put::<Pallet<T>>();
+
+			log::info!(target: "runtime::kitties", "migrated {} kitties to v4", migrated);
+
+			T::DbWeight::get().reads_writes(1 + migrated, 1 + migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() >= 4 {
+				return Ok(())
+			}
+
+			Self::set_temp_storage(kitty_count::<T>(), "v4_kitty_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// Later migrations in the same tuple have already run by now.
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 4, "expected storage version 4");
+
+			if let Some(count) = Self::get_temp_storage::<u32>("v4_kitty_count") {
+				ensure!(kitties_v2::<T>().count() as u32 == count, "migration dropped kitties");
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Records the birth block and breeding cooldown of every kitty.
+pub mod v5 {
+	use super::*;
+
+	/// Rewrites every kitty as born in block zero and free to breed, since neither was tracked
+	/// before.
+	pub struct MigrateToV5<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV5<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 4 {
+				log::info!(target: "runtime::kitties", "skipping v5 migration: not on v4");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = Pallet::<T>::name().as_bytes();
+			let old_kitties: Vec<(Dna<T>, KittyV4<T>)> =
+				storage_key_iter::<Dna<T>, KittyV4<T>, Blake2_128Concat>(pallet, b"Kitties")
+					.collect();
+			let migrated = old_kitties.len() as u64;
+			for (dna, old) in old_kitties {
+				let kitty = KittyV5::<T> {
+					dna: old.dna,
+					owner: old.owner,
+					price: old.price,
+					gender: old.gender,
+					parents: old.parents,
+					generation: old.generation,
+					creator: old.creator,
+					royalty: old.royalty,
+					collection: old.collection,
+					birth_block: Zero::zero(),
+					cooldown_until: Zero::zero(),
+				};
+				let key = Blake2_128Concat::hash(&dna.encode());
+				put_storage_value(pallet, b"Kitties", &key, kitty);
+			}
+
+			StorageVersion::new(5).put::<Pallet<T>>();
+
+			log::info!(target: "runtime::kitties", "migrated {} kitties to v5", migrated);
+
+			T::DbWeight::get().reads_writes(1 + migrated, 1 + migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() >= 5 {
+				return Ok(())
+			}
+
+			Self::set_temp_storage(kitty_count::<T>(), "v5_kitty_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// Later migrations in the same tuple have already run by now.
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 5, "expected storage version 5");
+
+			if let Some(count) = Self::get_temp_storage::<u32>("v5_kitty_count") {
+				ensure!(kitties_v2::<T>().count() as u32 == count, "migration dropped kitties");
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Records the block every offer was made in.
+pub mod v6 {
+	use super::*;
+
+	/// Dates every open offer to the upgrade block, so that offers on kitties that exist now
+	/// can still be accepted.
+	pub struct MigrateToV6<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV6<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 5 {
+				log::info!(target: "runtime::kitties", "skipping v6 migration: not on v5");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			let pallet = Pallet::<T>::name().as_bytes();
+			let old_offers: Vec<(Vec<u8>, OfferV5<T>)> =
+				storage_iter::<OfferV5<T>>(pallet, b"Offers").collect();
+			let migrated = old_offers.len() as u64;
+			for (key, old) in old_offers {
+				let offer = OfferV6::<T> { amount: old.amount, expiry: old.expiry, made_at: now };
+				put_storage_value(pallet, b"Offers", &key, offer);
+			}
+
+			StorageVersion::new(6).put::<Pallet<T>>();
+
+			log::info!(target: "runtime::kitties", "migrated {} offers to v6", migrated);
+
+			T::DbWeight::get().reads_writes(1 + migrated, 1 + migrated)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() >= 6 {
+				return Ok(())
+			}
+
+			// Only the keys are decoded, so offers in any earlier layout can be counted.
+			Self::set_temp_storage(<Offers<T>>::iter_keys().count() as u32, "v6_offer_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() == 6, "expected storage version 6");
+
+			if let Some(count) = Self::get_temp_storage::<u32>("v6_offer_count") {
+				ensure!(
+					<Offers<T>>::iter_keys().count() as u32 == count,
+					"migration dropped offers"
+				);
+			}
+			Ok(())
+		}
+	}
+}
+
+/// Counts the kitties of every collection that have not been burned.
+pub mod v7 {
+	use super::*;
+
+	/// Fills `CollectionSupply` from `Kitties`, so that collections created before it was
+	/// tracked can only be destroyed once they are empty.
+	pub struct MigrateToV7<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV7<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 6 {
+				log::info!(target: "runtime::kitties", "skipping v7 migration: not on v6");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let mut read = 0u64;
+			let mut supply: BTreeMap<CollectionId, u32> = BTreeMap::new();
+			for (_, kitty) in storage_key_iter::<Dna<T>, KittyV5<T>, Blake2_128Concat>(
+				Pallet::<T>::name().as_bytes(),
+				b"Kitties",
+			) {
+				read += 1;
+				if let Some(id) = kitty.collection {
+					*supply.entry(id).or_default() += 1;
+				}
+			}
+
+			let collections = supply.len() as u64;
+			for (id, count) in supply {
+				<CollectionSupply<T>>::insert(id, count);
+			}
+
+			StorageVersion::new(7).put::<Pallet<T>>();
+
+			log::info!(
+				target: "runtime::kitties",
+				"counted {} kitties into {} collections for v7",
+				read,
+				collections,
+			);
+
+			T::DbWeight::get().reads_writes(1 + read, 1 + collections)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			// Later migrations in the same tuple have already run by now.
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 7, "expected storage version 7");
+
+			let in_collections = storage_key_iter::<Dna<T>, KittyV5<T>, Blake2_128Concat>(
+				Pallet::<T>::name().as_bytes(),
+				b"Kitties",
+			)
+			.filter(|(_, kitty)| kitty.collection.is_some())
+			.count() as u32;
+			let counted = <CollectionSupply<T>>::iter_values().sum::<u32>();
+			ensure!(counted == in_collections, "collection supply does not match Kitties");
+			Ok(())
+		}
+	}
+}
+
+/// Records a mint nonce on every kitty and, on every offer, the nonce of the kitty it was made
+/// for.
+pub mod v8 {
+	use super::*;
+
+	/// Numbers every kitty in storage order and ties every offer to its kitty's number.
+	///
+	/// Offers made before their kitty was born were for an earlier kitty with the same DNA. They
+	/// get a nonce no kitty has, so they can only be withdrawn or expire.
+	pub struct MigrateToV8<T>(PhantomData<T>);
+
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV8<T> {
+		fn on_runtime_upgrade() -> Weight {
+			if Pallet::<T>::on_chain_storage_version() != 7 {
+				log::info!(target: "runtime::kitties", "skipping v8 migration: not on v7");
+				return T::DbWeight::get().reads(1)
+			}
+
+			let pallet = Pallet::<T>::name().as_bytes();
+			let old_kitties: Vec<(Dna<T>, KittyV5<T>)> =
+				storage_key_iter::<Dna<T>, KittyV5<T>, Blake2_128Concat>(pallet, b"Kitties")
+					.collect();
+			let kitties = old_kitties.len() as u64;
+			let mut born: BTreeMap<Vec<u8>, (u64, T::BlockNumber)> = BTreeMap::new();
+			for (mint_nonce, (dna, old)) in (0u64..).zip(old_kitties) {
+				born.insert(dna.to_vec(), (mint_nonce, old.birth_block));
+				let kitty = KittyV8::<T> {
+					dna: old.dna,
+					owner: old.owner,
+					price: old.price,
+					gender: old.gender,
+					parents: old.parents,
+					generation: old.generation,
+					creator: old.creator,
+					royalty: old.royalty,
+					collection: old.collection,
+					birth_block: old.birth_block,
+					cooldown_until: old.cooldown_until,
+					mint_nonce,
+				};
+				let key = Blake2_128Concat::hash(&dna.encode());
+				put_storage_value(pallet, b"Kitties", &key, kitty);
+			}
+			<NextMintNonce<T>>::put(kitties);
+
+			let old_offers: Vec<(Vec<u8>, OfferV6<T>)> =
+				storage_iter::<OfferV6<T>>(pallet, b"Offers").collect();
+			let offers = old_offers.len() as u64;
+			let mut outdated = 0u64;
+			for (key, old) in old_offers {
+				// The key starts with the 16 byte hash of the DNA, followed by the DNA itself.
+				let kitty = key.get(16..).and_then(|rest| Vec::<u8>::decode(&mut &rest[..]).ok());
+				let kitty_nonce = match kitty.and_then(|dna| born.get(&dna)) {
+					Some((nonce, birth_block)) if old.made_at >= *birth_block => *nonce,
+					_ => {
+						outdated += 1;
+						u64::MAX
+					},
+				};
+				let offer = OfferV8::<T> { amount: old.amount, expiry: old.expiry, kitty_nonce };
+				put_storage_value(pallet, b"Offers", &key, offer);
+			}
+
+			StorageVersion::new(8).put::<Pallet<T>>();
+
+			log::info!(
+				target: "runtime::kitties",
+				"migrated {} kitties and {} offers to v8, {} offers outdated",
+				kitties,
+				offers,
+				outdated,
+			);
+
+			T::DbWeight::get().reads_writes(1 + kitties + offers, 2 + kitties + offers)
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<(), &'static str> {
+			if Pallet::<T>::on_chain_storage_version() >= 8 {
+				return Ok(())
+			}
+
+			Self::set_temp_storage(kitty_count::<T>(), "v8_kitty_count");
+			// Only the keys are decoded, so offers in any earlier layout can be counted.
+			Self::set_temp_storage(<Offers<T>>::iter_keys().count() as u32, "v8_offer_count");
+			Ok(())
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade() -> Result<(), &'static str> {
+			ensure!(Pallet::<T>::on_chain_storage_version() >= 8, "expected storage version 8");
+
+			if let Some(count) = Self::get_temp_storage::<u32>("v8_kitty_count") {
+				ensure!(kitty_count::<T>() == count, "migration dropped kitties");
+				ensure!(
+					<NextMintNonce<T>>::get() >= count as u64,
+					"NextMintNonce is below the number of kitties"
+				);
+			}
+			if let Some(count) = Self::get_temp_storage::<u32>("v8_offer_count") {
+				ensure!(
+					<Offers<T>>::iter_keys().count() as u32 == count,
+					"migration dropped offers"
+				);
+			}
+			Ok(())
+		}
+	}
+}
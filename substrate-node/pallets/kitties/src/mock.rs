@@ -1,15 +1,23 @@
-use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
+use crate as pallet_kitties;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, GenesisBuild, Randomness},
+};
 use frame_system as system;
 use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	traits::{BlakeTwo256, Hash, IdentityLookup},
+	Permill,
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
 
+pub const ALICE: u64 = 1;
+pub const BOB: u64 = 2;
+pub const CHARLIE: u64 = 3;
+
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
 	pub enum Test where
@@ -18,7 +26,8 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
-		TemplateModule: pallet_template::{Pallet, Call, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		KittiesModule: pallet_kitties::{Pallet, Call, Storage, Config<T>, Event<T>},
 	}
 );
 
@@ -40,7 +49,7 @@ impl system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +58,86 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+// Deterministic randomness that still varies with the subject and the block number.
+pub struct TestRandomness;
+
+impl Randomness<H256, u64> for TestRandomness {
+	fn random(subject: &[u8]) -> (H256, u64) {
+		let block_number = System::block_number();
+		(BlakeTwo256::hash_of(&(subject, block_number)), block_number)
+	}
+}
+
+parameter_types! {
+	// Zero by default so that balances only move where a test asks for a deposit.
+	pub static KittyDeposit: u64 = 0;
+	pub static CollectionDeposit: u64 = 0;
+	pub static AllowDirectTransfer: bool = true;
+	pub const MaxRoyalty: Permill = Permill::from_percent(20);
+	pub CooldownSchedule: Vec<u64> = vec![2, 5, 10];
+}
+
+impl pallet_kitties::Config for Test {
+	type Event = Event;
+	type Currency = Balances;
+	type KittyRandomness = TestRandomness;
+	type GenomeDecoder = pallet_kitties::ByteGenomeDecoder;
+	type MaxDnaLen = ConstU32<32>;
+	type MaxKittiesOwned = ConstU32<4>;
+	type KittyDeposit = KittyDeposit;
+	type MaxAuctionsPerBlock = ConstU32<2>;
+	type CommitDeposit = ConstU64<5>;
+	type CommitPeriod = ConstU64<10>;
+	type MaxCommitsPerBlock = ConstU32<2>;
+	type AllowDirectTransfer = AllowDirectTransfer;
+	type MaxOffersPerBlock = ConstU32<2>;
+	type MaxRoyalty = MaxRoyalty;
+	type MaxLeasesPerBlock = ConstU32<2>;
+	type MaxNameLen = ConstU32<8>;
+	type MaxUriLen = ConstU32<16>;
+	type MaxAttributeKeyLen = ConstU32<4>;
+	type MaxAttributeValueLen = ConstU32<8>;
+	type MaxAttributes = ConstU32<2>;
+	type MetadataDepositBase = ConstU64<10>;
+	type AttributeDepositBase = ConstU64<5>;
+	type DepositPerByte = ConstU64<1>;
+	type CollectionDeposit = CollectionDeposit;
+	type CooldownSchedule = CooldownSchedule;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(ALICE, 1_000), (BOB, 1_000), (CHARLIE, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not recorded in block 0.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+// Like `new_test_ext`, with every deposit set, so that benchmarks are checked on the paths that
+// reserve them.
+#[cfg(feature = "runtime-benchmarks")]
+pub fn new_bench_ext() -> sp_io::TestExternalities {
+	KittyDeposit::set(10);
+	CollectionDeposit::set(10);
+	new_test_ext()
 }
@@ -1,20 +1,3183 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	genome::{EyeColour, FurColour, Pattern, Rarity},
+	mock::*,
+	AttributeCount, Attributes, Auctions, AuctionsEnding, ByteGenomeDecoder, CollectionDeposits,
+	Collections, Commitment, Commitments, CommitmentsExpiring, Dna, Error, FrozenKitties, Gender,
+	Genome, GenomeDecoder, IntegrityError, Kitties, KittiesForSale, KittiesOwned,
+	KittiesOwnedCount, KittyApprovals, KittyDeposits, LeaseOffers, Leases, LeasesExpiring,
+	LockedKitties, Metadata, MetadataInfo, MintPolicy, NextCollectionId, OfferExpiryCursor, Offers,
+	OffersExpiring, OperatorApprovals, PendingTransfers,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	traits::{
+		tokens::nonfungible::{Inspect, Mutate, Transfer},
+		GenesisBuild, Hooks, ReservableCurrency,
+	},
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BlakeTwo256, Hash},
+	DispatchError, DispatchResult, Permill,
+};
+
+fn dna(bytes: &[u8]) -> Dna<Test> {
+	bytes.to_vec().try_into().unwrap()
+}
+
+// The DNA of every kitty in the owner index of `who`, sorted, since storage order is by hash.
+fn owned(who: u64) -> Vec<Vec<u8>> {
+	let mut kitties: Vec<Vec<u8>> =
+		KittiesOwned::<Test>::iter_key_prefix(who).map(|dna| dna.into_inner()).collect();
+	kitties.sort();
+	kitties
+}
+
+// The hash `who` commits to before revealing `dna` with `salt`.
+fn commitment(who: u64, dna: &[u8], salt: u8) -> H256 {
+	BlakeTwo256::hash_of(&(who, dna.to_vec(), [salt; 32]))
+}
+
+// Commit `who` to `dna` with the salt `[0; 32]` as if in the genesis block, so that the DNA can be
+// minted straight away.
+fn commit(who: u64, dna: &[u8]) {
+	let pending = Commitment { deposit: 0, committed_at: 0, expiry: u64::MAX };
+	Commitments::<Test>::insert(who, commitment(who, dna, 0), pending);
+}
+
+fn create_kitty(who: u64, dna: Vec<u8>, price: u64, royalty: Permill) -> DispatchResult {
+	commit(who, &dna);
+	KittiesModule::create_kitty(Origin::signed(who), dna, [0; 32], price, royalty)
+}
+
+fn mint_into_collection(who: u64, collection: u32, dna: Vec<u8>) -> DispatchResult {
+	commit(who, &dna);
+	KittiesModule::mint_into_collection(
+		Origin::signed(who),
+		collection,
+		dna,
+		[0; 32],
+		Permill::zero(),
+	)
+}
+
+fn last_event() -> crate::Event<Test> {
+	match System::events().pop().expect("an event was emitted").event {
+		Event::KittiesModule(event) => event,
+		other => panic!("unexpected event {:?}", other),
+	}
+}
+
+fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		System::set_block_number(System::block_number() + 1);
+		KittiesModule::on_initialize(System::block_number());
+		KittiesModule::on_idle(System::block_number(), u64::MAX);
+	}
+}
+
+#[test]
+fn create_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+
+		let kitty = KittiesModule::kitties(dna(&[1, 2])).unwrap();
+		assert_eq!(kitty.owner, ALICE);
+		assert_eq!(kitty.price, 100);
+		assert_eq!(kitty.parents, None);
+		assert_eq!(kitty.generation, 0);
+		assert_eq!(owned(ALICE), vec![vec![1, 2]]);
+		assert_eq!(KittiesModule::number_of_kitties(), 1);
+		assert_eq!(last_event(), crate::Event::KittyStored(vec![1, 2], 100));
+	});
+}
+
+#[test]
+fn create_kitty_requires_a_commitment() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![1],
+				[7; 32],
+				100,
+				Permill::zero()
+			),
+			Error::<Test>::CommitmentNotExist
+		);
+
+		let hash = commitment(ALICE, &[1], 7);
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), hash));
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![1],
+				[7; 32],
+				100,
+				Permill::zero()
+			),
+			Error::<Test>::RevealTooEarly
+		);
+		// Someone who saw the DNA cannot mint it without a commitment of their own.
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(BOB),
+				vec![1],
+				[7; 32],
+				100,
+				Permill::zero()
+			),
+			Error::<Test>::CommitmentNotExist
+		);
+
+		run_to_block(2);
+		assert_ok!(KittiesModule::create_kitty(
+			Origin::signed(ALICE),
+			vec![1],
+			[7; 32],
+			100,
+			Permill::zero()
+		));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().price, 100);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(!Commitments::<Test>::contains_key(ALICE, hash));
+	});
+}
+
+#[test]
+fn create_kitty_of_existing_dna_mints_nothing() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![1, 2], 200, Permill::zero()));
+
+		let hash = commitment(BOB, &[1, 2], 0);
+		assert_eq!(last_event(), crate::Event::CommitmentRefunded(BOB, hash, 0));
+		assert!(!Commitments::<Test>::contains_key(BOB, hash));
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2])).unwrap().owner, ALICE);
+		assert_eq!(KittiesModule::number_of_kitties(), 1);
+	});
+}
+
+#[test]
+fn create_kitty_requires_price() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![1, 2],
+				[0; 32],
+				0,
+				Permill::zero()
+			),
+			Error::<Test>::KittyNotConfiguredPrice
+		);
+	});
+}
+
+#[test]
+fn create_kitty_rejects_long_dna() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![0; 33],
+				[0; 32],
+				100,
+				Permill::zero()
+			),
+			Error::<Test>::DnaTooLong
+		);
+	});
+}
+
+#[test]
+fn create_kitty_respects_max_kitties_owned() {
+	new_test_ext().execute_with(|| {
+		for i in 0..4u8 {
+			assert_ok!(create_kitty(ALICE, vec![i], 100, Permill::zero()));
+		}
+		commit(ALICE, &[4]);
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![4],
+				[0; 32],
+				100,
+				Permill::zero()
+			),
+			Error::<Test>::TooManyKittiesOwned
+		);
+		assert_eq!(KittiesModule::kitty_count_of(ALICE), 4);
+	});
+}
+
+#[test]
+fn gender_is_derived_from_dna() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![1, 2, 3], 100, Permill::zero()));
+
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2])).unwrap().gender, Gender::Male);
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2, 3])).unwrap().gender, Gender::Female);
+	});
+}
+
+#[test]
+fn byte_genome_decoder_reads_fixed_positions() {
+	assert_eq!(
+		ByteGenomeDecoder::decode(&[2, 1, 3, 200, 7]),
+		Genome {
+			gender: Gender::Female,
+			fur: FurColour::Ginger,
+			eyes: EyeColour::Blue,
+			pattern: Pattern::Tortoiseshell,
+			rarity: Rarity::Rare,
+		}
+	);
+	assert_eq!(
+		ByteGenomeDecoder::decode(&[11, 7, 9, 255]),
+		Genome {
+			gender: Gender::Female,
+			fur: FurColour::Chocolate,
+			eyes: EyeColour::Copper,
+			pattern: Pattern::Calico,
+			rarity: Rarity::Legendary,
+		}
+	);
+	// Missing bytes read as zero.
+	assert_eq!(
+		ByteGenomeDecoder::decode(&[]),
+		Genome {
+			gender: Gender::Male,
+			fur: FurColour::Black,
+			eyes: EyeColour::Green,
+			pattern: Pattern::Solid,
+			rarity: Rarity::Common,
+		}
+	);
+}
+
+#[test]
+fn genome_is_emitted_on_creation() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+
+		let genome = ByteGenomeDecoder::decode(&[1, 2]);
+		System::assert_has_event(crate::Event::KittyGenome(vec![1, 2], genome).into());
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2])).unwrap().gender, genome.gender);
+	});
+}
+
+#[test]
+fn change_kitty_owner_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, BOB);
+		assert_eq!(owned(ALICE), vec![vec![2]]);
+		assert_eq!(owned(BOB), vec![vec![1]]);
+		assert_eq!(KittiesModule::number_of_kitties(), 2);
+		assert_eq!(last_event(), crate::Event::KittyChangedOwner(vec![1], ALICE, BOB));
+	});
+}
+
+#[test]
+fn change_kitty_owner_fails_for_missing_or_foreign_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::KittyNotExist
+		);
+
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(BOB), vec![1], BOB),
+			Error::<Test>::KittyNotOwned
+		);
+	});
+}
+
+#[test]
+fn change_kitty_owner_respects_max_kitties_owned() {
+	new_test_ext().execute_with(|| {
+		for i in 0..4u8 {
+			assert_ok!(create_kitty(BOB, vec![i], 100, Permill::zero()));
+		}
+		assert_ok!(create_kitty(ALICE, vec![9], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![9], BOB),
+			Error::<Test>::TooManyKittiesOwned
+		);
+	});
+}
+
+#[test]
+fn set_price_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::set_price(Origin::signed(BOB), vec![1], 50),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::set_price(Origin::signed(ALICE), vec![1], 0),
+			Error::<Test>::KittyNotConfiguredPrice
+		);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(ALICE), vec![1], 50));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().price, 50);
+		assert_eq!(last_event(), crate::Event::KittyPriceSet(vec![1], 50));
+	});
+}
+
+#[test]
+fn list_and_delist_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::list_for_sale(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+		assert!(KittiesForSale::<Test>::contains_key(dna(&[1])));
+		assert_eq!(last_event(), crate::Event::KittyListed(vec![1], 100));
+
+		assert_ok!(KittiesModule::delist(Origin::signed(ALICE), vec![1]));
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+		assert_eq!(last_event(), crate::Event::KittyDelisted(vec![1]));
+
+		assert_noop!(
+			KittiesModule::delist(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::KittyNotForSale
+		);
+	});
+}
+
+#[test]
+fn unpriced_kitty_cannot_be_listed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::mint_random_kitty(Origin::signed(ALICE), Permill::zero()));
+		let kitty_dna = owned(ALICE)[0].clone();
+
+		assert_noop!(
+			KittiesModule::list_for_sale(Origin::signed(ALICE), kitty_dna),
+			Error::<Test>::KittyNotConfiguredPrice
+		);
+	});
+}
+
+#[test]
+fn buy_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_ok!(KittiesModule::buy_kitty(Origin::signed(BOB), vec![1], 150));
+
+		assert_eq!(Balances::free_balance(ALICE), 1_100);
+		assert_eq!(Balances::free_balance(BOB), 900);
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, BOB);
+		assert_eq!(owned(ALICE), Vec::<Vec<u8>>::new());
+		assert_eq!(owned(BOB), vec![vec![1]]);
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+		assert_eq!(last_event(), crate::Event::KittySold(vec![1], ALICE, BOB, 100));
+	});
+}
+
+#[test]
+fn buy_kitty_fails_for_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_noop!(
+			KittiesModule::buy_kitty(Origin::signed(BOB), vec![1], 100),
+			Error::<Test>::KittyNotForSale
+		);
+
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+		assert_noop!(
+			KittiesModule::buy_kitty(Origin::signed(ALICE), vec![1], 100),
+			Error::<Test>::BuyerIsKittyOwner
+		);
+		assert_noop!(
+			KittiesModule::buy_kitty(Origin::signed(BOB), vec![1], 99),
+			Error::<Test>::KittyBidPriceTooLow
+		);
+
+		assert_ok!(KittiesModule::set_price(Origin::signed(ALICE), vec![1], 5_000));
+		assert_noop!(
+			KittiesModule::buy_kitty(Origin::signed(BOB), vec![1], 5_000),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn create_kitty_records_creator_and_caps_royalty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::from_percent(20)));
+		let kitty = KittiesModule::kitties(dna(&[1])).unwrap();
+		assert_eq!(kitty.creator, ALICE);
+		assert_eq!(kitty.royalty, Permill::from_percent(20));
+
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![2],
+				[0; 32],
+				100,
+				Permill::from_percent(21)
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+
+		// Transfers keep the creator.
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().creator, ALICE);
+	});
+}
+
+#[test]
+fn every_minting_call_takes_a_royalty() {
+	new_test_ext().execute_with(|| {
+		let royalty = Permill::from_percent(20);
+		let too_high = Permill::from_percent(21);
+
+		commit(ALICE, &[1]);
+		assert_noop!(
+			KittiesModule::reveal_kitty(Origin::signed(ALICE), vec![1], [0; 32], too_high),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		assert_ok!(KittiesModule::reveal_kitty(Origin::signed(ALICE), vec![1], [0; 32], royalty));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().royalty, royalty);
+
+		assert_noop!(
+			KittiesModule::mint_random_kitty(Origin::signed(BOB), too_high),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		assert_ok!(KittiesModule::mint_random_kitty(Origin::signed(BOB), royalty));
+		let kitty = KittiesModule::kitties(dna(&owned(BOB)[0])).unwrap();
+		assert_eq!((kitty.creator, kitty.royalty), (BOB, royalty));
+
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::Public
+		));
+		commit(CHARLIE, &[2]);
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(CHARLIE),
+				0,
+				vec![2],
+				[0; 32],
+				too_high
+			),
+			Error::<Test>::RoyaltyTooHigh
+		);
+		assert_ok!(KittiesModule::mint_into_collection(
+			Origin::signed(CHARLIE),
+			0,
+			vec![2],
+			[0; 32],
+			royalty
+		));
+		let kitty = KittiesModule::kitties(dna(&[2])).unwrap();
+		assert_eq!((kitty.creator, kitty.royalty), (CHARLIE, royalty));
+	});
+}
+
+#[test]
+fn buy_kitty_pays_royalty_to_creator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::from_percent(10)));
+
+		// The creator selling their own kitty keeps the whole price.
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+		assert_ok!(KittiesModule::buy_kitty(Origin::signed(BOB), vec![1], 100));
+		assert_eq!(Balances::free_balance(ALICE), 1_100);
+		assert_eq!(Balances::free_balance(BOB), 900);
+
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(BOB), vec![1]));
+		assert_ok!(KittiesModule::buy_kitty(Origin::signed(CHARLIE), vec![1], 100));
+		assert_eq!(Balances::free_balance(ALICE), 1_110);
+		assert_eq!(Balances::free_balance(BOB), 990);
+		assert_eq!(Balances::free_balance(CHARLIE), 900);
+		System::assert_has_event(crate::Event::RoyaltyPaid(vec![1], ALICE, 10).into());
+		assert_eq!(last_event(), crate::Event::KittySold(vec![1], BOB, CHARLIE, 100));
+	});
+}
+
+#[test]
+fn transfer_delists_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+	});
+}
+
+#[test]
+fn breed_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4, 5], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+
+		let child_dna = owned(ALICE)
+			.into_iter()
+			.find(|kitty| *kitty != vec![1, 2] && *kitty != vec![3, 4, 5])
+			.unwrap();
+		let child = KittiesModule::kitties(dna(&child_dna)).unwrap();
+		assert_eq!(child.owner, ALICE);
+		assert_eq!(child.generation, 1);
+		assert_eq!(child.parents, Some((dna(&[1, 2]), dna(&[3, 4, 5]))));
+		assert!(child_dna.len() == 2 || child_dna.len() == 3);
+		assert_eq!(KittiesModule::number_of_kitties(), 3);
+		assert_eq!(
+			last_event(),
+			crate::Event::KittyBred(child_dna, ALICE, vec![1, 2], vec![3, 4, 5])
+		);
+	});
+}
+
+#[test]
+fn breeding_puts_parents_on_cooldown() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4, 5], 100, Permill::zero()));
+		let parent = KittiesModule::kitties(dna(&[1, 2])).unwrap();
+		assert_eq!((parent.birth_block, parent.cooldown_until), (1, 1));
+
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2])).unwrap().cooldown_until, 3);
+		assert_eq!(KittiesModule::kitties(dna(&[3, 4, 5])).unwrap().cooldown_until, 3);
+		let child_dna = owned(ALICE)
+			.into_iter()
+			.find(|kitty| *kitty != vec![1, 2] && *kitty != vec![3, 4, 5])
+			.unwrap();
+		let child = KittiesModule::kitties(dna(&child_dna)).unwrap();
+		assert_eq!((child.birth_block, child.cooldown_until), (1, 1));
+
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]),
+			Error::<Test>::KittyOnCooldown
+		);
+		System::set_block_number(2);
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![3, 4, 5], vec![1, 2]),
+			Error::<Test>::KittyOnCooldown
+		);
+		System::set_block_number(3);
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+	});
+}
+
+#[test]
+fn breeding_cooldown_grows_with_generation() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::mint(&ALICE, dna(&[1, 2]), 100, None, 2, Permill::zero(), None));
+		assert_ok!(KittiesModule::mint(
+			&ALICE,
+			dna(&[3, 4, 5]),
+			100,
+			None,
+			0,
+			Permill::zero(),
+			None
+		));
+
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2])).unwrap().cooldown_until, 11);
+		assert_eq!(KittiesModule::kitties(dna(&[3, 4, 5])).unwrap().cooldown_until, 3);
+
+		// Generations past the end of the schedule rest as long as the last one.
+		assert_eq!(KittiesModule::cooldown_of(1), 5);
+		assert_eq!(KittiesModule::cooldown_of(9), 10);
+	});
+}
+
+#[test]
+fn breed_kitty_requires_opposite_genders() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4]),
+			Error::<Test>::KittySameGender
+		);
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![1, 2]),
+			Error::<Test>::KittySameGender
+		);
+	});
+}
+
+#[test]
+fn breed_kitty_requires_approval_for_both_parents() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![3, 4, 5], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![9]),
+			Error::<Test>::KittyNotExist
+		);
+	});
+}
+
+#[test]
+fn approved_accounts_can_breed_into_their_own_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![3, 4, 5], 100, Permill::zero()));
+		assert_ok!(KittiesModule::approve(Origin::signed(ALICE), vec![1, 2], CHARLIE));
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(BOB), CHARLIE, true));
+
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(CHARLIE), vec![1, 2], vec![3, 4, 5]));
+
+		// The parents stay with their owners and the child goes to the caller.
+		assert_eq!(owned(ALICE), vec![vec![1, 2]]);
+		assert_eq!(owned(BOB), vec![vec![3, 4, 5]]);
+		let child_dna = owned(CHARLIE).pop().unwrap();
+		let child = KittiesModule::kitties(dna(&child_dna)).unwrap();
+		assert_eq!((child.owner, child.creator), (CHARLIE, CHARLIE));
+		assert_eq!(
+			last_event(),
+			crate::Event::KittyBred(child_dna, CHARLIE, vec![1, 2], vec![3, 4, 5])
+		);
+		assert_eq!(KittiesModule::kitty_approval(dna(&[1, 2])), Some(CHARLIE));
+	});
+}
+
+#[test]
+fn breed_kitty_rejects_locked_or_frozen_parents() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4, 5], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::freeze_kitty(Origin::root(), vec![3, 4, 5]));
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]),
+			Error::<Test>::Frozen
+		);
+		assert_ok!(KittiesModule::thaw_kitty(Origin::root(), vec![3, 4, 5]));
+
+		assert_ok!(KittiesModule::lock_kitty(Origin::signed(ALICE), vec![1, 2]));
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]),
+			Error::<Test>::Frozen
+		);
+		assert_ok!(KittiesModule::unlock_kitty(Origin::signed(ALICE), vec![1, 2]));
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+	});
+}
+
+#[test]
+fn breed_kitty_rejects_leased_parents() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4, 5], 100, Permill::zero()));
+		assert_ok!(KittiesModule::lend_kitty(Origin::signed(ALICE), vec![3, 4, 5], BOB, 10, 0));
+		assert_ok!(KittiesModule::accept_lease(Origin::signed(BOB), vec![3, 4, 5]));
+
+		// The borrower has the kitty's cooldown until the lease ends.
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]),
+			Error::<Test>::KittyLeased
+		);
+
+		run_to_block(10);
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+	});
+}
+
+#[test]
+fn mint_random_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::mint_random_kitty(Origin::signed(ALICE), Permill::zero()));
+		assert_ok!(KittiesModule::mint_random_kitty(Origin::signed(ALICE), Permill::zero()));
+
+		let kitties = owned(ALICE);
+		assert_eq!(kitties.len(), 2);
+		assert_ne!(kitties[0], kitties[1]);
+		assert_eq!(kitties[0].len(), 32);
+		assert_eq!(KittiesModule::kitties(dna(&kitties[0])).unwrap().price, 0);
+		assert_eq!(KittiesModule::number_of_kitties(), 2);
+	});
+}
+
+#[test]
+fn genesis_config_mints_kitties() {
+	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	crate::GenesisConfig::<Test> {
+		kitties: vec![(ALICE, vec![1, 2], 100), (ALICE, vec![3], 0), (BOB, vec![4], 50)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+
+	sp_io::TestExternalities::new(t).execute_with(|| {
+		assert_eq!(KittiesModule::number_of_kitties(), 3);
+		assert_eq!(owned(ALICE), vec![vec![1, 2], vec![3]]);
+		assert_eq!(owned(BOB), vec![vec![4]]);
+
+		let kitty = KittiesModule::kitties(dna(&[4])).unwrap();
+		assert_eq!(kitty.owner, BOB);
+		assert_eq!(kitty.price, 50);
+		assert_eq!(KittiesModule::kitties(dna(&[3])).unwrap().price, 0);
+	});
+}
+
+#[test]
+fn runtime_api_helpers_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 200, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![2]));
+
+		assert_eq!(KittiesModule::kitty_by_dna(vec![1]).unwrap().price, 100);
+		assert!(KittiesModule::kitty_by_dna(vec![3]).is_none());
+		assert!(KittiesModule::kitty_by_dna(vec![0; 33]).is_none());
+		let mut alice_kitties = KittiesModule::kitties_of(ALICE, None, 10);
+		alice_kitties.sort();
+		assert_eq!(alice_kitties, vec![vec![1], vec![2]]);
+		assert!(KittiesModule::kitties_of(BOB, None, 10).is_empty());
+		assert_eq!(KittiesModule::listed_kitties(), vec![(vec![2], 200)]);
+		assert_eq!(KittiesModule::genome(vec![2]).unwrap().gender, Gender::Male);
+		assert!(KittiesModule::genome(vec![3]).is_none());
+	});
+}
+
+#[test]
+fn kitties_of_pages_through_the_owner_index() {
+	new_test_ext().execute_with(|| {
+		for i in 1..=4u8 {
+			assert_ok!(create_kitty(ALICE, vec![i], 100, Permill::zero()));
+		}
+		assert_ok!(create_kitty(BOB, vec![5], 100, Permill::zero()));
+
+		let first = KittiesModule::kitties_of(ALICE, None, 3);
+		assert_eq!(first.len(), 3);
+		let second = KittiesModule::kitties_of(ALICE, first.last().cloned(), 3);
+		assert_eq!(second.len(), 1);
+		assert!(KittiesModule::kitties_of(ALICE, second.last().cloned(), 3).is_empty());
+
+		let mut all = [first, second].concat();
+		all.sort();
+		assert_eq!(all, vec![vec![1], vec![2], vec![3], vec![4]]);
+		assert!(KittiesModule::kitties_of(ALICE, None, 0).is_empty());
+		assert!(KittiesModule::kitties_of(ALICE, Some(vec![0; 33]), 3).is_empty());
+	});
+}
+
+#[test]
+fn owner_count_follows_transfers_and_burns() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_eq!(KittiesModule::kitty_count_of(ALICE), 2);
+
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert_eq!(KittiesModule::kitty_count_of(ALICE), 1);
+		assert_eq!(KittiesModule::kitty_count_of(BOB), 1);
+
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(ALICE), vec![2]));
+		assert_eq!(KittiesModule::kitty_count_of(ALICE), 0);
+		assert!(!KittiesOwnedCount::<Test>::contains_key(ALICE));
+	});
+}
+
+#[test]
+fn create_auction_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+
+		let auction = KittiesModule::auctions(dna(&[1])).unwrap();
+		assert_eq!(auction.seller, ALICE);
+		assert_eq!(auction.reserve, 50);
+		assert_eq!(auction.end, 11);
+		assert_eq!(auction.best_bid, None);
+		assert_eq!(AuctionsEnding::<Test>::get(11).into_inner(), vec![dna(&[1])]);
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+		assert_eq!(last_event(), crate::Event::AuctionCreated(vec![1], ALICE, 50, 11));
+	});
+}
+
+#[test]
+fn create_auction_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![9], 50, 10),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(BOB), vec![1], 50, 10),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 0),
+			Error::<Test>::InvalidAuctionDuration
+		);
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10),
+			Error::<Test>::KittyInAuction
+		);
+
+		// `MaxAuctionsPerBlock` is 2 in the mock.
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![2], 50, 10));
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![3], 50, 10),
+			Error::<Test>::TooManyAuctionsEnding
+		);
+	});
+}
+
+#[test]
+fn kitty_in_auction_cannot_be_transferred_or_listed() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::KittyInAuction
+		);
+		assert_noop!(
+			KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn bid_reserves_funds_and_refunds_outbid_bidder() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+
+		assert_ok!(KittiesModule::bid(Origin::signed(BOB), vec![1], 100));
+		assert_eq!(Balances::reserved_balance(BOB), 100);
+		assert_eq!(last_event(), crate::Event::BidPlaced(vec![1], BOB, 100));
+
+		assert_ok!(KittiesModule::bid(Origin::signed(CHARLIE), vec![1], 150));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 150);
+		assert_eq!(KittiesModule::auctions(dna(&[1])).unwrap().best_bid, Some((CHARLIE, 150)));
+	});
+}
+
+#[test]
+fn bid_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(BOB), vec![2], 100),
+			Error::<Test>::AuctionNotExist
+		);
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(ALICE), vec![1], 100),
+			Error::<Test>::BuyerIsKittyOwner
+		);
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(BOB), vec![1], 49),
+			Error::<Test>::BidTooLow
+		);
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(BOB), vec![1], 2_000),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(KittiesModule::bid(Origin::signed(BOB), vec![1], 100));
+		assert_noop!(
+			KittiesModule::bid(Origin::signed(CHARLIE), vec![1], 100),
+			Error::<Test>::BidTooLow
+		);
+	});
+}
+
+#[test]
+fn auction_settles_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_ok!(KittiesModule::bid(Origin::signed(BOB), vec![1], 100));
+		assert_ok!(KittiesModule::bid(Origin::signed(CHARLIE), vec![1], 150));
+
+		run_to_block(10);
+		assert!(Auctions::<Test>::contains_key(dna(&[1])));
+
+		run_to_block(11);
+		assert!(!Auctions::<Test>::contains_key(dna(&[1])));
+		assert!(!AuctionsEnding::<Test>::contains_key(11));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, CHARLIE);
+		assert_eq!(owned(ALICE), Vec::<Vec<u8>>::new());
+		assert_eq!(owned(CHARLIE), vec![vec![1]]);
+		assert_eq!(Balances::free_balance(ALICE), 1_150);
+		assert_eq!(Balances::free_balance(CHARLIE), 850);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+		assert_eq!(last_event(), crate::Event::AuctionSettled(vec![1], ALICE, CHARLIE, 150));
+	});
+}
+
+#[test]
+fn auction_without_bids_is_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+
+		run_to_block(11);
+		assert!(!Auctions::<Test>::contains_key(dna(&[1])));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, ALICE);
+		assert_eq!(last_event(), crate::Event::AuctionCancelled(vec![1]));
+
+		// The kitty can be traded again.
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+	});
+}
+
+#[test]
+fn auction_is_cancelled_when_winner_owns_too_many_kitties() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_ok!(KittiesModule::bid(Origin::signed(BOB), vec![1], 100));
+		for i in 10..14 {
+			assert_ok!(create_kitty(BOB, vec![i], 100, Permill::zero()));
+		}
+
+		run_to_block(11);
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, ALICE);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(last_event(), crate::Event::AuctionCancelled(vec![1]));
+	});
+}
+
+#[test]
+fn approved_account_can_transfer_once() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::approve(Origin::signed(ALICE), vec![1], BOB));
+		assert_eq!(KittiesModule::kitty_approval(dna(&[1])), Some(BOB));
+		assert_eq!(last_event(), crate::Event::KittyApproved(vec![1], ALICE, BOB));
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(BOB), vec![1], CHARLIE));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, CHARLIE);
+		assert_eq!(owned(ALICE), Vec::<Vec<u8>>::new());
+		assert_eq!(owned(CHARLIE), vec![vec![1]]);
+		assert!(!KittyApprovals::<Test>::contains_key(dna(&[1])));
+		assert_eq!(last_event(), crate::Event::KittyChangedOwner(vec![1], ALICE, CHARLIE));
+
+		// The approval did not survive the transfer.
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(BOB), vec![1], BOB),
+			Error::<Test>::NotApproved
+		);
+	});
+}
+
+#[test]
+fn approval_is_cleared_on_every_ownership_change() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::approve(Origin::signed(ALICE), vec![1], CHARLIE));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert!(!KittyApprovals::<Test>::contains_key(dna(&[1])));
+
+		assert_ok!(KittiesModule::approve(Origin::signed(BOB), vec![1], CHARLIE));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(BOB), vec![1]));
+		assert_ok!(KittiesModule::buy_kitty(Origin::signed(ALICE), vec![1], 100));
+		assert!(!KittyApprovals::<Test>::contains_key(dna(&[1])));
+	});
+}
+
+#[test]
+fn operator_can_transfer_and_approve_all_kitties() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(ALICE), BOB, true));
+		assert!(OperatorApprovals::<Test>::contains_key(ALICE, BOB));
+		assert_eq!(last_event(), crate::Event::ApprovalForAll(ALICE, BOB, true));
+
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(BOB), vec![1], CHARLIE));
+		assert_ok!(KittiesModule::approve(Origin::signed(BOB), vec![2], CHARLIE));
+		assert_eq!(last_event(), crate::Event::KittyApproved(vec![2], ALICE, CHARLIE));
+
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(ALICE), BOB, false));
+		assert!(!OperatorApprovals::<Test>::contains_key(ALICE, BOB));
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(BOB), vec![2], BOB),
+			Error::<Test>::NotApproved
+		);
+		// The single-kitty approval given by the operator still stands.
+		assert_ok!(KittiesModule::transfer_from(Origin::signed(CHARLIE), vec![2], CHARLIE));
+	});
+}
+
+#[test]
+fn approvals_handle_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::approve(Origin::signed(ALICE), vec![9], BOB),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::approve(Origin::signed(BOB), vec![1], BOB),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(BOB), vec![1], BOB),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(KittiesModule::approve(Origin::signed(ALICE), vec![1], BOB));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(BOB), vec![1], BOB),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn offer_and_accept_transfer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB));
+		assert_eq!(last_event(), crate::Event::TransferOffered(vec![1], ALICE, BOB));
+		assert_eq!(KittiesModule::pending_transfer(dna(&[1])), Some(BOB));
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+		// Nothing moves until the recipient accepts.
+		assert_eq!(owned(ALICE), vec![vec![1]]);
+
+		assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyChangedOwner(vec![1], ALICE, BOB));
+		assert_eq!(owned(ALICE), Vec::<Vec<u8>>::new());
+		assert_eq!(owned(BOB), vec![vec![1]]);
+		assert!(!PendingTransfers::<Test>::contains_key(dna(&[1])));
+	});
+}
+
+#[test]
+fn transfer_offer_can_be_rejected_or_cancelled() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB));
+
+		assert_noop!(
+			KittiesModule::accept_transfer(Origin::signed(CHARLIE), vec![1]),
+			Error::<Test>::NotTransferRecipient
+		);
+		assert_noop!(
+			KittiesModule::reject_transfer(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::NotTransferRecipient
+		);
+		assert_ok!(KittiesModule::reject_transfer(Origin::signed(BOB), vec![1]));
+		assert_eq!(last_event(), crate::Event::TransferRejected(vec![1], ALICE, BOB));
+		assert!(!PendingTransfers::<Test>::contains_key(dna(&[1])));
+
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB));
+		assert_noop!(
+			KittiesModule::cancel_transfer(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(KittiesModule::cancel_transfer(Origin::signed(ALICE), vec![1]));
+		assert_eq!(last_event(), crate::Event::TransferCancelled(vec![1], ALICE, BOB));
+
+		assert_noop!(
+			KittiesModule::accept_transfer(Origin::signed(BOB), vec![1]),
+			Error::<Test>::TransferNotExist
+		);
+		assert_noop!(
+			KittiesModule::cancel_transfer(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::TransferNotExist
+		);
+		assert_eq!(owned(ALICE), vec![vec![1]]);
+	});
+}
+
+#[test]
+fn offer_transfer_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(ALICE), vec![9], BOB),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(BOB), vec![1], CHARLIE),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], ALICE),
+			Error::<Test>::TransferToSelf
+		);
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn pending_transfer_locks_the_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4, 5], 100, Permill::zero()));
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1, 2], BOB));
+
+		assert_noop!(
+			KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1, 2]),
+			Error::<Test>::TransferPending
+		);
+		assert_noop!(
+			KittiesModule::breed_kitty(Origin::signed(ALICE), vec![3, 4, 5], vec![1, 2]),
+			Error::<Test>::TransferPending
+		);
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1, 2], CHARLIE),
+			Error::<Test>::TransferPending
+		);
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![1, 2], 50, 10),
+			Error::<Test>::TransferPending
+		);
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1, 2], CHARLIE),
+			Error::<Test>::TransferPending
+		);
+		assert_noop!(
+			KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1, 2]),
+			Error::<Test>::TransferPending
+		);
+
+		// Burning the kitty by force drops the offer.
+		assert_ok!(KittiesModule::force_burn(Origin::root(), vec![1, 2]));
+		assert!(!PendingTransfers::<Test>::contains_key(dna(&[1, 2])));
+	});
+}
+
+#[test]
+fn direct_transfer_can_be_disabled() {
+	new_test_ext().execute_with(|| {
+		AllowDirectTransfer::set(false);
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::DirectTransferDisabled
+		);
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::DirectTransferDisabled
+		);
+		assert_ok!(KittiesModule::set_approval_for_all(Origin::signed(ALICE), CHARLIE, true));
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(CHARLIE), vec![1], BOB),
+			Error::<Test>::DirectTransferDisabled
+		);
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB));
+		assert_ok!(KittiesModule::accept_transfer(Origin::signed(BOB), vec![1]));
+		assert_eq!(owned(BOB), vec![vec![1]]);
+	});
+}
+
+#[test]
+fn concurrent_offers_reserve_funds_and_one_can_be_accepted() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+		assert_eq!(last_event(), crate::Event::OfferMade(vec![1], BOB, 60, 10));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![1], 80, 10));
+		assert_eq!(Balances::reserved_balance(BOB), 60);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 80);
+
+		// The owner may take any of the offers, not only the best one.
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], BOB));
+		assert_eq!(last_event(), crate::Event::KittySold(vec![1], ALICE, BOB, 60));
+		assert_eq!(owned(BOB), vec![vec![1]]);
+		assert_eq!(Balances::free_balance(ALICE), 1_060);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::free_balance(BOB), 940);
+		assert!(!Offers::<Test>::contains_key(dna(&[1]), BOB));
+		assert_eq!(OffersExpiring::<Test>::get(10).len(), 1);
+
+		// The other offer is now an offer to the new owner.
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(BOB), vec![1], CHARLIE));
+		assert_eq!(owned(CHARLIE), vec![vec![1]]);
+		assert_eq!(Balances::free_balance(BOB), 1_020);
+	});
+}
+
+#[test]
+fn auctions_and_offers_pay_royalty_to_creator() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::from_percent(20)));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(BOB), vec![1], 50, 10));
+		assert_ok!(KittiesModule::bid(Origin::signed(CHARLIE), vec![1], 100));
+		run_to_block(11);
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, CHARLIE);
+		assert_eq!(Balances::free_balance(ALICE), 1_020);
+		assert_eq!(Balances::free_balance(BOB), 1_080);
+		assert_eq!(Balances::free_balance(CHARLIE), 900);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 50, 20));
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(CHARLIE), vec![1], BOB));
+		assert_eq!(Balances::free_balance(ALICE), 1_030);
+		assert_eq!(Balances::free_balance(CHARLIE), 940);
+		assert_eq!(Balances::free_balance(BOB), 1_030);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(last_event(), crate::Event::KittySold(vec![1], CHARLIE, BOB, 50));
+	});
+}
+
+#[test]
+fn offer_can_be_withdrawn() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+
+		assert_ok!(KittiesModule::withdraw_offer(Origin::signed(BOB), vec![1]));
+		assert_eq!(last_event(), crate::Event::OfferWithdrawn(vec![1], BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(OffersExpiring::<Test>::get(10).is_empty());
+		assert_noop!(
+			KittiesModule::withdraw_offer(Origin::signed(BOB), vec![1]),
+			Error::<Test>::OfferNotExist
+		);
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::OfferNotExist
+		);
+	});
+}
+
+#[test]
+fn sales_fail_when_the_payment_is_no_longer_reserved() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![2], 10, 5));
+		assert_ok!(KittiesModule::bid(Origin::signed(CHARLIE), vec![2], 50));
+
+		// Part of each reserve is slashed elsewhere before the sale.
+		let _ = Balances::slash_reserved(&BOB, 30);
+		let _ = Balances::slash_reserved(&CHARLIE, 30);
+
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::PaymentNotReserved
+		);
+
+		// The auction is called off rather than handing the kitty over for less than the bid.
+		run_to_block(6);
+		assert_eq!(last_event(), crate::Event::AuctionCancelled(vec![2]));
+		assert_eq!(owned(ALICE), vec![vec![1], vec![2]]);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Balances::free_balance(CHARLIE), 970);
+	});
+}
+
+#[test]
+fn offers_do_not_carry_over_to_a_kitty_reminted_in_the_same_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+
+		// Burned and minted again without moving to the next block.
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]));
+		assert_ok!(create_kitty(CHARLIE, vec![1], 100, Permill::zero()));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().birth_block, 1);
+
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(CHARLIE), vec![1], BOB),
+			Error::<Test>::OfferOutdated
+		);
+	});
+}
+
+#[test]
+fn offers_do_not_carry_over_to_a_reminted_kitty() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+		let mint_nonce = KittiesModule::kitties(dna(&[1])).unwrap().mint_nonce;
+		assert_eq!(KittiesModule::offers(dna(&[1]), BOB).unwrap().kitty_nonce, mint_nonce);
+
+		run_to_block(2);
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]));
+		assert_ok!(create_kitty(CHARLIE, vec![1], 100, Permill::zero()));
+
+		// The offer was for Alice's kitty, so Charlie cannot sell his through it.
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(CHARLIE), vec![1], BOB),
+			Error::<Test>::OfferOutdated
+		);
+		assert_ok!(KittiesModule::withdraw_offer(Origin::signed(BOB), vec![1]));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+
+		// Offers made once the new kitty exists are fine.
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(CHARLIE), vec![1], BOB));
+		assert_eq!(owned(BOB), vec![vec![1]]);
+	});
+}
+
+#[test]
+fn expired_offers_are_refunded_on_idle() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 3));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![2], 70, 3));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![1], 50, 5));
+
+		run_to_block(2);
+		assert_eq!(Balances::reserved_balance(BOB), 60);
+
+		// Without enough weight nothing is refunded and the cursor stays put.
+		System::set_block_number(3);
+		assert_eq!(KittiesModule::on_idle(3, 0), 0);
+		assert_eq!(OfferExpiryCursor::<Test>::get(), 3);
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::OfferExpired
+		);
+
+		KittiesModule::on_idle(3, u64::MAX);
+		assert_eq!(OfferExpiryCursor::<Test>::get(), 4);
+		assert_eq!(last_event(), crate::Event::OfferExpired(vec![2], CHARLIE));
+		assert!(!Offers::<Test>::contains_key(dna(&[1]), BOB));
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 50);
+
+		run_to_block(5);
+		assert_eq!(Balances::reserved_balance(CHARLIE), 0);
+		assert_eq!(Offers::<Test>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn make_offer_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(BOB), vec![9], 60, 10),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(ALICE), vec![1], 60, 10),
+			Error::<Test>::BuyerIsKittyOwner
+		);
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(BOB), vec![1], 0, 10),
+			Error::<Test>::BidTooLow
+		);
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 1),
+			Error::<Test>::InvalidOfferExpiry
+		);
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(BOB), vec![1], 2_000, 10),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(BOB), vec![1], 70, 11),
+			Error::<Test>::OfferAlreadyExist
+		);
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![1], 60, 10));
+		assert_ok!(create_kitty(BOB, vec![2], 100, Permill::zero()));
+		assert_noop!(
+			KittiesModule::make_offer(Origin::signed(CHARLIE), vec![2], 60, 10),
+			Error::<Test>::TooManyOffersExpiring
+		);
+
+		// Only the owner can accept, and not while the kitty is locked.
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(CHARLIE), vec![1], BOB),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 5));
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn lend_and_accept_lease_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_ok!(KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], BOB, 10, 30));
+		assert_eq!(last_event(), crate::Event::LeaseOffered(vec![1], ALICE, BOB, 10, 30));
+		assert_eq!(KittiesModule::kitty_user(vec![1]), Some(ALICE));
+
+		assert_ok!(KittiesModule::accept_lease(Origin::signed(BOB), vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyLeased(vec![1], ALICE, BOB, 10));
+		assert_eq!(Balances::free_balance(ALICE), 1_030);
+		assert_eq!(Balances::free_balance(BOB), 970);
+		assert_eq!(KittiesModule::kitty_user(vec![1]), Some(BOB));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, ALICE);
+		assert!(LeaseOffers::<Test>::get(dna(&[1])).is_none());
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+
+		// The owner keeps title, but cannot sell or move the kitty while the lease runs.
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], CHARLIE),
+			Error::<Test>::KittyLeased
+		);
+		assert_noop!(
+			KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::KittyLeased
+		);
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 5),
+			Error::<Test>::KittyLeased
+		);
+		assert_noop!(
+			KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::KittyLeased
+		);
+		assert_noop!(
+			KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], CHARLIE, 20, 0),
+			Error::<Test>::KittyLeased
+		);
+
+		run_to_block(9);
+		assert_eq!(KittiesModule::kitty_user(vec![1]), Some(BOB));
+
+		run_to_block(10);
+		assert!(Leases::<Test>::get(dna(&[1])).is_none());
+		assert!(LeasesExpiring::<Test>::get(10).is_empty());
+		assert_eq!(last_event(), crate::Event::LeaseEnded(vec![1], BOB));
+		assert_eq!(KittiesModule::kitty_user(vec![1]), Some(ALICE));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], CHARLIE));
+	});
+}
+
+#[test]
+fn lease_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::lend_kitty(Origin::signed(ALICE), vec![9], BOB, 10, 0),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::lend_kitty(Origin::signed(BOB), vec![1], CHARLIE, 10, 0),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], ALICE, 10, 0),
+			Error::<Test>::LendToSelf
+		);
+		assert_noop!(
+			KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], BOB, 1, 0),
+			Error::<Test>::InvalidLeaseExpiry
+		);
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(BOB), vec![1]),
+			Error::<Test>::LeaseNotExist
+		);
+
+		// Only the named borrower can accept, and only the owner can cancel.
+		assert_ok!(KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], BOB, 10, 2_000));
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(CHARLIE), vec![1]),
+			Error::<Test>::NotLeaseBorrower
+		);
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(BOB), vec![1]),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			KittiesModule::cancel_lease(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(KittiesModule::cancel_lease(Origin::signed(ALICE), vec![1]));
+		assert_eq!(last_event(), crate::Event::LeaseCancelled(vec![1], ALICE, BOB));
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(BOB), vec![1]),
+			Error::<Test>::LeaseNotExist
+		);
+
+		// A lease offer does not survive a change of owner.
+		assert_ok!(KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], BOB, 10, 0));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], CHARLIE));
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(BOB), vec![1]),
+			Error::<Test>::LeaseNotExist
+		);
+
+		// An offer cannot be accepted once its end block has started.
+		assert_ok!(KittiesModule::lend_kitty(Origin::signed(CHARLIE), vec![1], BOB, 5, 0));
+		run_to_block(5);
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(BOB), vec![1]),
+			Error::<Test>::InvalidLeaseExpiry
+		);
+
+		// At most `MaxLeasesPerBlock` leases end in the same block.
+		for i in 2..5 {
+			assert_ok!(create_kitty(ALICE, vec![i], 100, Permill::zero()));
+			assert_ok!(KittiesModule::lend_kitty(Origin::signed(ALICE), vec![i], BOB, 10, 0));
+		}
+		assert_ok!(KittiesModule::accept_lease(Origin::signed(BOB), vec![2]));
+		assert_ok!(KittiesModule::accept_lease(Origin::signed(BOB), vec![3]));
+		assert_noop!(
+			KittiesModule::accept_lease(Origin::signed(BOB), vec![4]),
+			Error::<Test>::TooManyLeasesExpiring
+		);
+	});
+}
+
+#[test]
+fn force_burn_ends_lease() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], BOB, 10, 0));
+		assert_ok!(KittiesModule::accept_lease(Origin::signed(BOB), vec![1]));
+
+		assert_ok!(KittiesModule::force_burn(Origin::root(), vec![1]));
+
+		assert!(Leases::<Test>::get(dna(&[1])).is_none());
+		assert!(LeasesExpiring::<Test>::get(10).is_empty());
+		assert_eq!(KittiesModule::kitty_user(vec![1]), None);
+	});
+}
+
+#[test]
+fn kitty_metadata_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_ok!(KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![1], b"Tom".to_vec()));
+		assert_eq!(last_event(), crate::Event::KittyNamed(vec![1], b"Tom".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 13);
+
+		// The name and URI share one deposit, sized by both.
+		assert_ok!(KittiesModule::set_metadata(
+			Origin::signed(ALICE),
+			vec![1],
+			b"ipfs://x".to_vec()
+		));
+		assert_eq!(last_event(), crate::Event::MetadataSet(vec![1], b"ipfs://x".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 21);
+
+		assert_ok!(KittiesModule::set_attribute(
+			Origin::signed(ALICE),
+			vec![1],
+			b"eye".to_vec(),
+			b"blue".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 33);
+		assert_ok!(KittiesModule::set_attribute(
+			Origin::signed(ALICE),
+			vec![1],
+			b"eye".to_vec(),
+			b"green".to_vec()
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::AttributeSet(vec![1], b"eye".to_vec(), b"green".to_vec())
+		);
+		assert_eq!(Balances::reserved_balance(ALICE), 34);
+		assert_eq!(AttributeCount::<Test>::get(dna(&[1])), 1);
+		assert_eq!(
+			KittiesModule::metadata(vec![1]),
+			Some(MetadataInfo {
+				name: b"Tom".to_vec(),
+				uri: b"ipfs://x".to_vec(),
+				attributes: vec![(b"eye".to_vec(), b"green".to_vec())],
+			})
+		);
+		assert_eq!(
+			<KittiesModule as Inspect<u64>>::attribute(&vec![1], b"eye"),
+			Some(b"green".to_vec())
+		);
+
+		assert_ok!(KittiesModule::clear_attribute(Origin::signed(ALICE), vec![1], b"eye".to_vec()));
+		assert_eq!(last_event(), crate::Event::AttributeCleared(vec![1], b"eye".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 21);
+		assert_eq!(AttributeCount::<Test>::get(dna(&[1])), 0);
+		assert_noop!(
+			KittiesModule::clear_attribute(Origin::signed(ALICE), vec![1], b"eye".to_vec()),
+			Error::<Test>::AttributeNotExist
+		);
+
+		assert_ok!(KittiesModule::clear_metadata(Origin::signed(ALICE), vec![1]));
+		assert_eq!(last_event(), crate::Event::MetadataCleared(vec![1]));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(KittiesModule::metadata(vec![1]), Some(MetadataInfo::default()));
+		assert_noop!(
+			KittiesModule::clear_metadata(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::MetadataNotExist
+		);
+		assert_eq!(KittiesModule::metadata(vec![9]), None);
+	});
+}
+
+#[test]
+fn kitty_metadata_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![9], b"Tom".to_vec()),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::set_kitty_name(Origin::signed(BOB), vec![1], b"Tom".to_vec()),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::set_attribute(Origin::signed(BOB), vec![1], b"eye".to_vec(), vec![]),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_noop!(
+			KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![1], vec![0; 9]),
+			Error::<Test>::NameTooLong
+		);
+		assert_noop!(
+			KittiesModule::set_metadata(Origin::signed(ALICE), vec![1], vec![0; 17]),
+			Error::<Test>::UriTooLong
+		);
+		assert_noop!(
+			KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![0; 5], vec![]),
+			Error::<Test>::AttributeKeyTooLong
+		);
+		assert_noop!(
+			KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![0], vec![0; 9]),
+			Error::<Test>::AttributeValueTooLong
+		);
+
+		assert_ok!(KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![0], vec![]));
+		assert_ok!(KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![1], vec![]));
+		assert_noop!(
+			KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![2], vec![]),
+			Error::<Test>::TooManyAttributes
+		);
+		// Existing attributes can still be changed.
+		assert_ok!(KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![1], vec![1]));
+	});
+}
+
+#[test]
+fn metadata_deposits_stay_with_depositor_until_released() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![1], b"Tom".to_vec()));
+		assert_ok!(KittiesModule::set_attribute(
+			Origin::signed(ALICE),
+			vec![1],
+			b"a".to_vec(),
+			b"b".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 20);
+
+		// Metadata stays with the kitty, and the deposits with the account that paid them.
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert_eq!(KittiesModule::metadata(vec![1]).unwrap().name, b"Tom".to_vec());
+		assert_eq!(Balances::reserved_balance(ALICE), 20);
+
+		// Replacing the name moves its deposit to the new owner.
+		assert_ok!(KittiesModule::set_kitty_name(Origin::signed(BOB), vec![1], b"Jerry".to_vec()));
+		assert_eq!(Balances::reserved_balance(ALICE), 7);
+		assert_eq!(Balances::reserved_balance(BOB), 15);
+
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(BOB), vec![1]));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::reserved_balance(BOB), 0);
+		assert!(Metadata::<Test>::get(dna(&[1])).is_none());
+		assert_eq!(Attributes::<Test>::iter_prefix(dna(&[1])).count(), 0);
+		assert_eq!(AttributeCount::<Test>::get(dna(&[1])), 0);
+	});
+}
+
+#[test]
+fn collection_mint_policies_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			Some(3),
+			50,
+			MintPolicy::OwnerOnly
+		));
+		assert_eq!(last_event(), crate::Event::CollectionCreated(0, ALICE));
+		assert_eq!(KittiesModule::collections(0).unwrap().owner, ALICE);
+
+		// The owner mints for free.
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(BOB),
+				0,
+				vec![1],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::MintNotAllowed
+		);
+		assert_ok!(mint_into_collection(ALICE, 0, vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyMinted(vec![1], 0, ALICE));
+		let kitty = KittiesModule::kitties(dna(&[1])).unwrap();
+		assert_eq!((kitty.owner, kitty.creator, kitty.collection), (ALICE, ALICE, Some(0)));
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+
+		// Allow-listed accounts pay the mint price to the owner.
+		assert_ok!(KittiesModule::set_collection_mint(
+			Origin::signed(ALICE),
+			0,
+			Some(3),
+			50,
+			MintPolicy::AllowList
+		));
+		assert_eq!(
+			last_event(),
+			crate::Event::CollectionMintSet(0, Some(3), 50, MintPolicy::AllowList)
+		);
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(BOB),
+				0,
+				vec![2],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::MintNotAllowed
+		);
+		assert_ok!(KittiesModule::set_allow_list(Origin::signed(ALICE), 0, BOB, true));
+		assert_eq!(last_event(), crate::Event::AllowListSet(0, BOB, true));
+		assert_ok!(mint_into_collection(BOB, 0, vec![2]));
+		assert_eq!(Balances::free_balance(BOB), 950);
+		assert_eq!(Balances::free_balance(ALICE), 1_050);
+
+		// Anyone may mint into a public collection until it is full.
+		assert_ok!(KittiesModule::set_collection_mint(
+			Origin::signed(ALICE),
+			0,
+			Some(3),
+			50,
+			MintPolicy::Public
+		));
+		assert_ok!(mint_into_collection(CHARLIE, 0, vec![3]));
+		assert_eq!(KittiesModule::collections(0).unwrap().minted, 3);
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(ALICE),
+				0,
+				vec![4],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::CollectionFull
+		);
+
+		// Burned kitties still count towards the max supply.
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(CHARLIE), vec![3]));
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(ALICE),
+				0,
+				vec![4],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::CollectionFull
+		);
+		assert_noop!(
+			KittiesModule::set_collection_mint(
+				Origin::signed(ALICE),
+				0,
+				Some(2),
+				50,
+				MintPolicy::Public
+			),
+			Error::<Test>::MaxSupplyTooLow
+		);
+		assert_ok!(KittiesModule::set_collection_mint(
+			Origin::signed(ALICE),
+			0,
+			None,
+			50,
+			MintPolicy::Public
+		));
+		assert_ok!(mint_into_collection(ALICE, 0, vec![4]));
+	});
+}
+
+#[test]
+fn creating_a_collection_reserves_the_deposit() {
+	new_test_ext().execute_with(|| {
+		CollectionDeposit::set(20);
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::Public
+		));
+		assert_eq!(Balances::reserved_balance(ALICE), 20);
+		assert_eq!(CollectionDeposits::<Test>::get(0), Some((ALICE, 20)));
+
+		CollectionDeposit::set(2_000);
+		assert_noop!(
+			KittiesModule::create_collection(Origin::signed(BOB), None, 0, MintPolicy::Public),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn destroying_an_empty_collection_returns_the_deposit() {
+	new_test_ext().execute_with(|| {
+		CollectionDeposit::set(20);
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::Public
+		));
+		assert_ok!(mint_into_collection(BOB, 0, vec![1]));
+		assert_eq!(KittiesModule::collection_supply(0), 1);
+
+		assert_noop!(
+			KittiesModule::destroy_collection(Origin::signed(BOB), 0),
+			Error::<Test>::NotCollectionOwner
+		);
+		assert_noop!(
+			KittiesModule::destroy_collection(Origin::signed(ALICE), 0),
+			Error::<Test>::CollectionNotEmpty
+		);
+
+		// Burned kitties still count as minted, but no longer keep the collection alive.
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(BOB), vec![1]));
+		assert_eq!(KittiesModule::collection_supply(0), 0);
+		assert_eq!(KittiesModule::collections(0).unwrap().minted, 1);
+		assert_ok!(KittiesModule::destroy_collection(Origin::signed(ALICE), 0));
+		assert_eq!(last_event(), crate::Event::CollectionDestroyed(0, ALICE));
+
+		assert_eq!(KittiesModule::collections(0), None);
+		assert_eq!(CollectionDeposits::<Test>::get(0), None);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_noop!(
+			KittiesModule::destroy_collection(Origin::signed(ALICE), 0),
+			Error::<Test>::CollectionNotExist
+		);
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(BOB),
+				0,
+				vec![2],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::CollectionNotExist
+		);
+	});
+}
+
+#[test]
+fn minted_count_cannot_overflow() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::Public
+		));
+		Collections::<Test>::mutate(0, |details| details.as_mut().unwrap().minted = u32::MAX);
+
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(ALICE),
+				0,
+				vec![1],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::CollectionFull
+		);
+	});
+}
+
+#[test]
+fn collection_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(ALICE),
+				0,
+				vec![1],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::CollectionNotExist
+		);
+		assert_noop!(
+			KittiesModule::set_collection_mint(
+				Origin::signed(ALICE),
+				0,
+				None,
+				0,
+				MintPolicy::Public
+			),
+			Error::<Test>::CollectionNotExist
+		);
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::Public
+		));
+		assert_noop!(
+			KittiesModule::set_collection_mint(
+				Origin::signed(BOB),
+				0,
+				None,
+				0,
+				MintPolicy::OwnerOnly
+			),
+			Error::<Test>::NotCollectionAdmin
+		);
+		assert_noop!(
+			KittiesModule::set_allow_list(Origin::signed(BOB), 0, BOB, true),
+			Error::<Test>::NotCollectionAdmin
+		);
+		assert_noop!(
+			KittiesModule::freeze_collection_metadata(Origin::signed(BOB), 0),
+			Error::<Test>::NotCollectionOwner
+		);
+
+		// Minting follows the same rules as any other kitty.
+		assert_ok!(mint_into_collection(BOB, 0, vec![1]));
+		assert_ok!(mint_into_collection(BOB, 0, vec![1]));
+		assert_eq!(
+			last_event(),
+			crate::Event::CommitmentRefunded(BOB, commitment(BOB, &[1], 0), 0)
+		);
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(BOB),
+				0,
+				vec![0; 33],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::DnaTooLong
+		);
+		assert_eq!(KittiesModule::collections(0).unwrap().minted, 1);
+
+		NextCollectionId::<Test>::put(u32::MAX);
+		assert_noop!(
+			KittiesModule::create_collection(Origin::signed(ALICE), None, 0, MintPolicy::Public),
+			Error::<Test>::NoAvailableCollectionId
+		);
+	});
+}
+
+#[test]
+fn collection_admins_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			50,
+			MintPolicy::OwnerOnly
+		));
+		assert_noop!(
+			KittiesModule::set_collection_admin(Origin::signed(BOB), 0, BOB, true),
+			Error::<Test>::NotCollectionOwner
+		);
+		assert_ok!(KittiesModule::set_collection_admin(Origin::signed(ALICE), 0, BOB, true));
+		assert_eq!(last_event(), crate::Event::CollectionAdminSet(0, BOB, true));
+
+		// Admins mint for free and manage the allow-list, but cannot appoint admins.
+		assert_ok!(mint_into_collection(BOB, 0, vec![1]));
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+		assert_ok!(KittiesModule::set_allow_list(Origin::signed(BOB), 0, CHARLIE, true));
+		assert_noop!(
+			KittiesModule::set_collection_admin(Origin::signed(BOB), 0, CHARLIE, true),
+			Error::<Test>::NotCollectionOwner
+		);
+		assert_noop!(
+			KittiesModule::freeze_collection_metadata(Origin::signed(BOB), 0),
+			Error::<Test>::NotCollectionOwner
+		);
+
+		assert_ok!(KittiesModule::set_collection_admin(Origin::signed(ALICE), 0, BOB, false));
+		assert_eq!(last_event(), crate::Event::CollectionAdminSet(0, BOB, false));
+		assert_noop!(
+			KittiesModule::mint_into_collection(
+				Origin::signed(BOB),
+				0,
+				vec![2],
+				[0; 32],
+				Permill::zero()
+			),
+			Error::<Test>::MintNotAllowed
+		);
+	});
+}
+
+#[test]
+fn frozen_collection_metadata_cannot_change() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::OwnerOnly
+		));
+		assert_ok!(mint_into_collection(ALICE, 0, vec![1]));
+		assert_ok!(KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![1], b"Tom".to_vec()));
+		assert_ok!(KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![0], vec![]));
+		assert_eq!(Balances::reserved_balance(ALICE), 19);
+
+		assert_ok!(KittiesModule::freeze_collection_metadata(Origin::signed(ALICE), 0));
+		assert_eq!(last_event(), crate::Event::CollectionMetadataFrozen(0));
+		assert!(KittiesModule::collections(0).unwrap().metadata_frozen);
+
+		assert_noop!(
+			KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![1], b"Jerry".to_vec()),
+			Error::<Test>::MetadataFrozen
+		);
+		assert_noop!(
+			KittiesModule::set_metadata(Origin::signed(ALICE), vec![1], b"ipfs".to_vec()),
+			Error::<Test>::MetadataFrozen
+		);
+		assert_noop!(
+			KittiesModule::clear_metadata(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::MetadataFrozen
+		);
+		assert_noop!(
+			KittiesModule::set_attribute(Origin::signed(ALICE), vec![1], vec![1], vec![]),
+			Error::<Test>::MetadataFrozen
+		);
+		assert_noop!(
+			KittiesModule::clear_attribute(Origin::signed(ALICE), vec![1], vec![0]),
+			Error::<Test>::MetadataFrozen
+		);
+
+		// Kitties outside the collection are not affected, and burning still refunds deposits.
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(KittiesModule::set_kitty_name(Origin::signed(ALICE), vec![2], b"Tom".to_vec()));
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]));
+		assert_eq!(Balances::reserved_balance(ALICE), 13);
+	});
+}
+
+#[test]
+fn lock_kitty_blocks_transfers_and_sales() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_noop!(
+			KittiesModule::lock_kitty(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(KittiesModule::lock_kitty(Origin::signed(ALICE), vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyLocked(vec![1], ALICE));
+		assert!(LockedKitties::<Test>::contains_key(dna(&[1])));
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::transfer_from(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 10, 5),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::lend_kitty(Origin::signed(ALICE), vec![1], BOB, 10, 5),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::Frozen
+		);
+
+		assert_noop!(
+			KittiesModule::unlock_kitty(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(KittiesModule::unlock_kitty(Origin::signed(ALICE), vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyUnlocked(vec![1], ALICE));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert_noop!(
+			KittiesModule::unlock_kitty(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotLocked
+		);
+	});
+}
+
+#[test]
+fn lock_kitty_blocks_pending_transfers_and_offers() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+
+		// Transfers and offers made before the lock cannot complete while it holds.
+		assert_ok!(KittiesModule::offer_transfer(Origin::signed(ALICE), vec![1], BOB));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![1], 50, 10));
+		assert_ok!(KittiesModule::lock_kitty(Origin::signed(ALICE), vec![1]));
+		assert_noop!(
+			KittiesModule::accept_transfer(Origin::signed(BOB), vec![1]),
+			Error::<Test>::Frozen
+		);
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], CHARLIE),
+			Error::<Test>::Frozen
+		);
+
+		// Locking cannot be used to call off an auction.
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![2], 10, 5));
+		assert_noop!(
+			KittiesModule::lock_kitty(Origin::signed(ALICE), vec![2]),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn freeze_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 10, 5));
+		assert_ok!(KittiesModule::bid(Origin::signed(BOB), vec![1], 50));
+
+		assert_noop!(
+			KittiesModule::freeze_kitty(Origin::signed(ALICE), vec![1]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::freeze_kitty(Origin::root(), vec![9]),
+			Error::<Test>::KittyNotExist
+		);
+		// The running auction is called off and the bid refunded.
+		assert_ok!(KittiesModule::freeze_kitty(Origin::root(), vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyFrozen(vec![1]));
+		assert!(KittiesModule::auctions(dna(&[1])).is_none());
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::Frozen
+		);
+		// The owner cannot lift a freeze.
+		assert_noop!(
+			KittiesModule::unlock_kitty(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::KittyNotLocked
+		);
+		assert_ok!(KittiesModule::lock_kitty(Origin::signed(ALICE), vec![1]));
+		assert_ok!(KittiesModule::unlock_kitty(Origin::signed(ALICE), vec![1]));
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::Frozen
+		);
+
+		assert_noop!(
+			KittiesModule::thaw_kitty(Origin::signed(ALICE), vec![1]),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(KittiesModule::thaw_kitty(Origin::root(), vec![1]));
+		assert_eq!(last_event(), crate::Event::KittyThawed(vec![1]));
+		assert_noop!(
+			KittiesModule::thaw_kitty(Origin::root(), vec![1]),
+			Error::<Test>::KittyNotFrozen
+		);
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+	});
+}
+
+#[test]
+fn force_burn_clears_freeze_and_lock() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::lock_kitty(Origin::signed(ALICE), vec![1]));
+		assert_ok!(KittiesModule::freeze_kitty(Origin::root(), vec![1]));
+
+		assert_ok!(KittiesModule::force_burn(Origin::root(), vec![1]));
+		assert!(!LockedKitties::<Test>::contains_key(dna(&[1])));
+		assert!(!FrozenKitties::<Test>::contains_key(dna(&[1])));
+
+		// A kitty minted again with the same DNA starts out unlocked.
+		assert_ok!(create_kitty(BOB, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(BOB), vec![1], ALICE));
+	});
+}
+
+#[test]
+fn burn_kitty_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+		assert_ok!(KittiesModule::approve(Origin::signed(ALICE), vec![1], BOB));
+
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]));
+
+		assert!(!Kitties::<Test>::contains_key(dna(&[1])));
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+		assert!(!KittyApprovals::<Test>::contains_key(dna(&[1])));
+		assert_eq!(owned(ALICE), vec![vec![2]]);
+		assert_eq!(KittiesModule::number_of_kitties(), 1);
+		assert_eq!(last_event(), crate::Event::KittyBurned(vec![1], ALICE));
+
+		// The DNA is free again.
+		assert_ok!(create_kitty(BOB, vec![1], 100, Permill::zero()));
+		assert_eq!(KittiesModule::number_of_kitties(), 2);
+	});
+}
+
+#[test]
+fn burn_kitty_refunds_creation_deposit() {
+	new_test_ext().execute_with(|| {
+		KittyDeposit::set(10);
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_eq!(Balances::reserved_balance(ALICE), 10);
+		assert_eq!(KittyDeposits::<Test>::get(dna(&[1])), Some((ALICE, 10)));
+
+		// The deposit stays with the account that paid it when the kitty changes owner.
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB));
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(BOB), vec![1]));
+
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+		assert!(!KittyDeposits::<Test>::contains_key(dna(&[1])));
+	});
+}
+
+#[test]
+fn creating_a_kitty_requires_the_deposit() {
+	new_test_ext().execute_with(|| {
+		KittyDeposit::set(2_000);
+		commit(ALICE, &[1]);
+		assert_noop!(
+			KittiesModule::create_kitty(
+				Origin::signed(ALICE),
+				vec![1],
+				[0; 32],
+				100,
+				Permill::zero()
+			),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_noop!(
+			KittiesModule::mint_random_kitty(Origin::signed(ALICE), Permill::zero()),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+	});
+}
+
+#[test]
+fn burn_kitty_handles_bad_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+
+		assert_noop!(
+			KittiesModule::burn_kitty(Origin::signed(ALICE), vec![9]),
+			Error::<Test>::KittyNotExist
+		);
+		assert_noop!(
+			KittiesModule::burn_kitty(Origin::signed(BOB), vec![1]),
+			Error::<Test>::KittyNotOwned
+		);
+
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_noop!(
+			KittiesModule::burn_kitty(Origin::signed(ALICE), vec![1]),
+			Error::<Test>::KittyInAuction
+		);
+	});
+}
+
+#[test]
+fn force_burn_requires_root() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_noop!(
+			KittiesModule::force_burn(Origin::signed(ALICE), vec![1]),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			KittiesModule::force_burn(Origin::root(), vec![9]),
+			Error::<Test>::KittyNotExist
+		);
+	});
+}
+
+#[test]
+fn force_burn_cancels_auction_and_refunds_bid() {
+	new_test_ext().execute_with(|| {
+		KittyDeposit::set(10);
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![1], 50, 10));
+		assert_ok!(KittiesModule::bid(Origin::signed(BOB), vec![1], 100));
+
+		assert_ok!(KittiesModule::force_burn(Origin::root(), vec![1]));
+
+		assert!(!Kitties::<Test>::contains_key(dna(&[1])));
+		assert!(!Auctions::<Test>::contains_key(dna(&[1])));
+		assert!(AuctionsEnding::<Test>::get(11).is_empty());
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+		assert_eq!(Balances::free_balance(BOB), 1_000);
+		assert_eq!(KittiesModule::number_of_kitties(), 0);
+		assert_eq!(last_event(), crate::Event::KittyBurned(vec![1], ALICE));
+
+		// Settlement finds nothing left to do.
+		run_to_block(11);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+	});
+}
+
+#[test]
+fn nonfungible_inspect_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(KittiesModule::create_auction(Origin::signed(ALICE), vec![2], 50, 10));
+
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&vec![1]), Some(ALICE));
+		assert_eq!(<KittiesModule as Inspect<u64>>::owner(&vec![9]), None);
+		assert!(<KittiesModule as Inspect<u64>>::can_transfer(&vec![1]));
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&vec![2]));
+		assert!(!<KittiesModule as Inspect<u64>>::can_transfer(&vec![9]));
+	});
+}
+
+#[test]
+fn nonfungible_transfer_works() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![1]));
+
+		assert_ok!(<KittiesModule as Transfer<u64>>::transfer(&vec![1], &BOB));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, BOB);
+		assert_eq!(owned(BOB), vec![vec![1]]);
+		assert!(!KittiesForSale::<Test>::contains_key(dna(&[1])));
+		assert_eq!(last_event(), crate::Event::KittyChangedOwner(vec![1], ALICE, BOB));
+
+		assert_noop!(
+			<KittiesModule as Transfer<u64>>::transfer(&vec![9], &BOB),
+			Error::<Test>::KittyNotExist
+		);
+	});
+}
+
+#[test]
+fn nonfungible_mint_and_burn_work() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&vec![1], &ALICE));
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().price, 0);
+		assert_eq!(KittiesModule::number_of_kitties(), 1);
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::mint_into(&vec![1], &BOB),
+			Error::<Test>::KittyAlreadyExist
+		);
+
+		assert_noop!(
+			<KittiesModule as Mutate<u64>>::burn(&vec![1], Some(&BOB)),
+			Error::<Test>::KittyNotOwned
+		);
+		assert_ok!(<KittiesModule as Mutate<u64>>::burn(&vec![1], Some(&ALICE)));
+		assert_eq!(KittiesModule::number_of_kitties(), 0);
+		assert_eq!(owned(ALICE), Vec::<Vec<u8>>::new());
+
+		assert_ok!(<KittiesModule as Mutate<u64>>::mint_into(&vec![2], &BOB));
+		assert_ok!(<KittiesModule as Mutate<u64>>::burn(&vec![2], None));
+		assert_eq!(last_event(), crate::Event::KittyBurned(vec![2], BOB));
+	});
+}
+
+#[test]
+fn commit_and_reveal_mints_kitty() {
+	new_test_ext().execute_with(|| {
+		let hash = commitment(ALICE, &[1, 2], 7);
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), hash));
+		assert_eq!(last_event(), crate::Event::KittyCommitted(ALICE, hash, 11));
+		assert_eq!(Balances::reserved_balance(ALICE), 5);
+		assert_eq!(KittiesModule::commitments(ALICE, hash).unwrap().expiry, 11);
+
+		// Not in the block the commitment was made in.
+		assert_noop!(
+			KittiesModule::reveal_kitty(
+				Origin::signed(ALICE),
+				vec![1, 2],
+				[7; 32],
+				Permill::zero()
+			),
+			Error::<Test>::RevealTooEarly
+		);
+
+		run_to_block(2);
+		assert_ok!(KittiesModule::reveal_kitty(
+			Origin::signed(ALICE),
+			vec![1, 2],
+			[7; 32],
+			Permill::zero()
+		));
+
+		assert_eq!(last_event(), crate::Event::KittyStored(vec![1, 2], 0));
+		assert_eq!(owned(ALICE), vec![vec![1, 2]]);
+		assert_eq!(KittiesModule::kitties(dna(&[1, 2])).unwrap().price, 0);
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert!(!Commitments::<Test>::contains_key(ALICE, hash));
+		assert!(CommitmentsExpiring::<Test>::get(11).is_empty());
+	});
+}
+
+#[test]
+fn reveal_requires_a_matching_commitment() {
+	new_test_ext().execute_with(|| {
+		let hash = commitment(ALICE, &[1], 7);
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), hash));
+		assert_noop!(
+			KittiesModule::commit_kitty(Origin::signed(ALICE), hash),
+			Error::<Test>::CommitmentAlreadyExist
+		);
+		run_to_block(2);
+
+		assert_noop!(
+			KittiesModule::reveal_kitty(Origin::signed(ALICE), vec![1], [8; 32], Permill::zero()),
+			Error::<Test>::CommitmentNotExist
+		);
+		assert_noop!(
+			KittiesModule::reveal_kitty(Origin::signed(ALICE), vec![2], [7; 32], Permill::zero()),
+			Error::<Test>::CommitmentNotExist
+		);
+		// Copying the hash does not let another account reveal the DNA first.
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(BOB), hash));
+		run_to_block(3);
+		assert_noop!(
+			KittiesModule::reveal_kitty(Origin::signed(BOB), vec![1], [7; 32], Permill::zero()),
+			Error::<Test>::CommitmentNotExist
+		);
+		assert_noop!(
+			KittiesModule::reveal_kitty(
+				Origin::signed(ALICE),
+				vec![0; 33],
+				[7; 32],
+				Permill::zero()
+			),
+			Error::<Test>::DnaTooLong
+		);
+		assert_ok!(KittiesModule::reveal_kitty(
+			Origin::signed(ALICE),
+			vec![1],
+			[7; 32],
+			Permill::zero()
+		));
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn reveal_of_existing_dna_refunds_the_commitment() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(TemplateModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		let hash = commitment(ALICE, &[1], 7);
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), hash));
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(BOB), commitment(BOB, &[1], 8)));
+		run_to_block(2);
+		assert_ok!(KittiesModule::reveal_kitty(
+			Origin::signed(BOB),
+			vec![1],
+			[8; 32],
+			Permill::zero()
+		));
+
+		// The DNA went to whoever revealed first; the other commitment is not slashed.
+		assert_ok!(KittiesModule::reveal_kitty(
+			Origin::signed(ALICE),
+			vec![1],
+			[7; 32],
+			Permill::zero()
+		));
+		assert_eq!(last_event(), crate::Event::CommitmentRefunded(ALICE, hash, 5));
+		assert!(!Commitments::<Test>::contains_key(ALICE, hash));
+		assert!(CommitmentsExpiring::<Test>::get(11).is_empty());
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 1_000);
+		assert_eq!(KittiesModule::kitties(dna(&[1])).unwrap().owner, BOB);
+	});
+}
+
+#[test]
+fn expired_commitment_is_slashed() {
+	new_test_ext().execute_with(|| {
+		let hash = commitment(ALICE, &[1], 7);
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), hash));
+		let issuance = Balances::total_issuance();
+
+		run_to_block(10);
+		assert!(Commitments::<Test>::contains_key(ALICE, hash));
+		run_to_block(11);
+
+		assert!(!Commitments::<Test>::contains_key(ALICE, hash));
+		assert_eq!(last_event(), crate::Event::CommitmentExpired(ALICE, hash, 5));
+		assert_eq!(Balances::reserved_balance(ALICE), 0);
+		assert_eq!(Balances::free_balance(ALICE), 995);
+		assert_eq!(Balances::total_issuance(), issuance - 5);
+		assert_noop!(
+			KittiesModule::reveal_kitty(Origin::signed(ALICE), vec![1], [7; 32], Permill::zero()),
+			Error::<Test>::CommitmentNotExist
+		);
+	});
+}
+
+#[test]
+fn commit_kitty_respects_max_commits_per_block() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), commitment(ALICE, &[1], 0)));
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(ALICE), commitment(ALICE, &[2], 0)));
+		assert_noop!(
+			KittiesModule::commit_kitty(Origin::signed(BOB), commitment(BOB, &[3], 0)),
+			Error::<Test>::TooManyCommitmentsExpiring
+		);
+
+		// Commitments made in the next block expire in the next block too.
+		run_to_block(2);
+		assert_ok!(KittiesModule::commit_kitty(Origin::signed(BOB), commitment(BOB, &[3], 0)));
+	});
+}
+
+#[test]
+fn migrate_to_v1_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	// Kitty as stored from v1 until v3: DNA, owner, price, gender, parents and generation.
+	type V1Kitty = (Vec<u8>, u64, u64, Gender, Option<(Vec<u8>, Vec<u8>)>, u32);
+
+	// Kitty as stored before v1: raw DNA, owner, `u32` price and gender.
+	fn put_old_kitty(dna: &[u8], owner: u64, price: u32) {
+		let key = Blake2_128Concat::hash(&dna.to_vec().encode());
+		let old_kitty = (dna.to_vec(), owner, price, Gender::Male);
+		put_storage_value(b"KittiesModule", b"Kitties", &key, old_kitty);
+	}
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<KittiesModule>();
+		put_old_kitty(&[1, 2], ALICE, 100);
+		put_old_kitty(&[3], ALICE, 200);
+		put_old_kitty(&[4], BOB, 300);
+		// Longer than `MaxDnaLen`, so it cannot be kept.
+		put_old_kitty(&[5; 33], BOB, 400);
+		// More than `MaxKittiesOwned` for Charlie, who keeps them all.
+		for i in 6..11u8 {
+			put_old_kitty(&[i], CHARLIE, 10);
+		}
+		let put_old_owned = |owner: u64, kitties: Vec<Vec<u8>>| {
+			let key = Blake2_128Concat::hash(&owner.encode());
+			put_storage_value(b"KittiesModule", b"KittiesOwned", &key, kitties);
+		};
+		put_old_owned(ALICE, vec![vec![1, 2], vec![3]]);
+		put_old_owned(BOB, vec![vec![4], vec![5; 33]]);
+		put_old_owned(CHARLIE, (6..11u8).map(|i| vec![i]).collect());
+		crate::NumOfKitties::<Test>::put(9);
+
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 1);
+		assert_eq!(KittiesModule::number_of_kitties(), 8);
+		// Kitties are written in their v1 layout, without creator and royalty, for `MigrateToV3`.
+		let v1_kitty = |bytes: &[u8]| {
+			let key = Blake2_128Concat::hash(&bytes.to_vec().encode());
+			get_storage_value::<V1Kitty>(b"KittiesModule", b"Kitties", &key).unwrap()
+		};
+		let (_, owner, price, gender, parents, generation) = v1_kitty(&[1, 2]);
+		assert_eq!((owner, price, gender), (ALICE, 100, Gender::Male));
+		assert_eq!((parents, generation), (None, 0));
+		assert_eq!(v1_kitty(&[3]).3, Gender::Female);
+		assert_eq!(v1_kitty(&[4]).2, 300);
+		// The owner index is left in its v1 layout for `MigrateToV2`.
+		let old_owned = |owner: u64| {
+			let key = Blake2_128Concat::hash(&owner.encode());
+			get_storage_value::<Vec<Vec<u8>>>(b"KittiesModule", b"KittiesOwned", &key)
+		};
+		let mut alice_kitties = old_owned(ALICE).unwrap();
+		alice_kitties.sort();
+		assert_eq!(alice_kitties, vec![vec![1, 2], vec![3]]);
+		assert_eq!(old_owned(BOB), Some(vec![vec![4]]));
+		assert_eq!(old_owned(CHARLIE).unwrap().len(), 5);
+
+		// Running it again changes nothing.
+		crate::migrations::v1::MigrateToV1::<Test>::on_runtime_upgrade();
+		assert_eq!(KittiesModule::number_of_kitties(), 8);
+	});
+}
+
+#[test]
+fn migrate_to_v2_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![4], 100, Permill::zero()));
+
+		// Replace the owner index with its v1 layout: a list of DNA per owner.
+		let _ = KittiesOwned::<Test>::remove_all(None);
+		let _ = KittiesOwnedCount::<Test>::remove_all(None);
+		StorageVersion::new(1).put::<KittiesModule>();
+		let put_old_owned = |owner: u64, kitties: Vec<Vec<u8>>| {
+			let key = Blake2_128Concat::hash(&owner.encode());
+			put_storage_value(b"KittiesModule", b"KittiesOwned", &key, kitties);
+		};
+		put_old_owned(ALICE, vec![vec![1, 2], vec![3]]);
+		put_old_owned(BOB, vec![vec![4]]);
+
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 2);
+		assert_eq!(owned(ALICE), vec![vec![1, 2], vec![3]]);
+		assert_eq!(owned(BOB), vec![vec![4]]);
+		assert_eq!(KittiesModule::kitty_count_of(ALICE), 2);
+		assert_eq!(KittiesModule::kitty_count_of(BOB), 1);
+		assert_eq!(KittiesModule::integrity_errors(), vec![]);
+
+		// Running it again changes nothing.
+		crate::migrations::v2::MigrateToV2::<Test>::on_runtime_upgrade();
+		assert_eq!(KittiesModule::kitty_count_of(ALICE), 2);
+	});
+}
+
+#[test]
+fn migrate_to_v3_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	// Kitty as stored in v3: the v1 fields followed by creator and royalty.
+	type V3Kitty = (Vec<u8>, u64, u64, Gender, Option<(Vec<u8>, Vec<u8>)>, u32, u64, Permill);
+	let v3_kitty = |bytes: &[u8]| {
+		let key = Blake2_128Concat::hash(&dna(bytes).encode());
+		get_storage_value::<V3Kitty>(b"KittiesModule", b"Kitties", &key).unwrap()
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![2], 200, Permill::zero()));
+
+		// Rewrite every kitty in its v2 layout, without creator and royalty.
+		StorageVersion::new(2).put::<KittiesModule>();
+		for (dna, kitty) in Kitties::<Test>::iter().collect::<Vec<_>>() {
+			let key = Blake2_128Concat::hash(&dna.encode());
+			let old_kitty = (
+				kitty.dna,
+				kitty.owner,
+				kitty.price,
+				kitty.gender,
+				kitty.parents,
+				kitty.generation,
+			);
+			put_storage_value(b"KittiesModule", b"Kitties", &key, old_kitty);
+		}
+
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 3);
+		// Kitties are written in their v3 layout, without a collection, for `MigrateToV4`.
+		let (_, owner, price, _, _, _, creator, royalty) = v3_kitty(&[1]);
+		assert_eq!((owner, creator, royalty), (ALICE, ALICE, Permill::zero()));
+		assert_eq!(price, 100);
+		assert_eq!(v3_kitty(&[2]).6, BOB);
+
+		// Running it again changes nothing.
+		crate::migrations::v3::MigrateToV3::<Test>::on_runtime_upgrade();
+		assert_eq!(v3_kitty(&[2]).6, BOB);
+	});
+}
+
+#[test]
+fn migrate_to_v4_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	// Kitty as stored in v4: the v3 fields followed by the collection.
+	type V4Kitty =
+		(Vec<u8>, u64, u64, Gender, Option<(Vec<u8>, Vec<u8>)>, u32, u64, Permill, Option<u32>);
+	let v4_kitty = |bytes: &[u8]| {
+		let key = Blake2_128Concat::hash(&dna(bytes).encode());
+		get_storage_value::<V4Kitty>(b"KittiesModule", b"Kitties", &key).unwrap()
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::from_percent(5)));
+		assert_ok!(create_kitty(BOB, vec![2], 200, Permill::zero()));
+
+		// Rewrite every kitty in its v3 layout, without a collection.
+		StorageVersion::new(3).put::<KittiesModule>();
+		for (dna, kitty) in Kitties::<Test>::iter().collect::<Vec<_>>() {
+			let key = Blake2_128Concat::hash(&dna.encode());
+			let old_kitty = (
+				kitty.dna,
+				kitty.owner,
+				kitty.price,
+				kitty.gender,
+				kitty.parents,
+				kitty.generation,
+				kitty.creator,
+				kitty.royalty,
+			);
+			put_storage_value(b"KittiesModule", b"Kitties", &key, old_kitty);
+		}
+
+		crate::migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 4);
+		// Kitties are written in their v4 layout, without birth block and cooldown, for
+		// `MigrateToV5`.
+		let (_, owner, price, _, _, _, creator, royalty, collection) = v4_kitty(&[1]);
+		assert_eq!((owner, creator, royalty), (ALICE, ALICE, Permill::from_percent(5)));
+		assert_eq!((price, collection), (100, None));
+		assert_eq!(v4_kitty(&[2]).1, BOB);
+
+		// Running it again changes nothing.
+		crate::migrations::v4::MigrateToV4::<Test>::on_runtime_upgrade();
+		assert_eq!(v4_kitty(&[2]).1, BOB);
+	});
+}
+
+#[test]
+fn migrate_to_v5_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	// Kitty as stored in v5: the v4 fields followed by the birth block and cooldown.
+	type V5Kitty = (
+		Vec<u8>,
+		u64,
+		u64,
+		Gender,
+		Option<(Vec<u8>, Vec<u8>)>,
+		u32,
+		u64,
+		Permill,
+		Option<u32>,
+		u64,
+		u64,
+	);
+	let v5_kitty = |bytes: &[u8]| {
+		let key = Blake2_128Concat::hash(&dna(bytes).encode());
+		get_storage_value::<V5Kitty>(b"KittiesModule", b"Kitties", &key).unwrap()
+	};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(7);
+		assert_ok!(KittiesModule::create_collection(
+			Origin::signed(ALICE),
+			None,
+			0,
+			MintPolicy::Public
+		));
+		assert_ok!(mint_into_collection(ALICE, 0, vec![1]));
+		assert_ok!(create_kitty(BOB, vec![2], 200, Permill::zero()));
+
+		// Rewrite every kitty in its v4 layout, without birth block and cooldown.
+		StorageVersion::new(4).put::<KittiesModule>();
+		for (dna, kitty) in Kitties::<Test>::iter().collect::<Vec<_>>() {
+			let key = Blake2_128Concat::hash(&dna.encode());
+			let old_kitty = (
+				kitty.dna,
+				kitty.owner,
+				kitty.price,
+				kitty.gender,
+				kitty.parents,
+				kitty.generation,
+				kitty.creator,
+				kitty.royalty,
+				kitty.collection,
+			);
+			put_storage_value(b"KittiesModule", b"Kitties", &key, old_kitty);
+		}
+
+		crate::migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 5);
+		// Kitties are written in their v5 layout, without a mint nonce, for `MigrateToV8`.
+		let (_, owner, _, _, _, _, _, _, collection, birth_block, cooldown_until) = v5_kitty(&[1]);
+		assert_eq!((owner, collection), (ALICE, Some(0)));
+		assert_eq!((birth_block, cooldown_until), (0, 0));
+		assert_eq!(v5_kitty(&[2]).2, 200);
+
+		// Running it again changes nothing.
+		crate::migrations::v5::MigrateToV5::<Test>::on_runtime_upgrade();
+		assert_eq!(v5_kitty(&[2]).2, 200);
+	});
+}
+
+#[test]
+fn migrate_to_v6_works() {
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::{get_storage_value, put_storage_value},
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	// Offer as stored in v6: amount, expiry and the block it was made in.
+	let v6_offer = |bidder: u64| {
+		let mut key = Blake2_128Concat::hash(&dna(&[1]).encode());
+		key.extend(Blake2_128Concat::hash(&bidder.encode()));
+		get_storage_value::<(u64, u64, u64)>(b"KittiesModule", b"Offers", &key).unwrap()
+	};
+
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(BOB), vec![1], 60, 10));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![1], 80, 12));
+
+		// Rewrite every offer in its v5 layout, without the block it was made in.
+		StorageVersion::new(5).put::<KittiesModule>();
+		for (dna, bidder, offer) in Offers::<Test>::iter().collect::<Vec<_>>() {
+			let mut key = Blake2_128Concat::hash(&dna.encode());
+			key.extend(Blake2_128Concat::hash(&bidder.encode()));
+			put_storage_value(b"KittiesModule", b"Offers", &key, (offer.amount, offer.expiry));
+		}
+
+		System::set_block_number(4);
+		crate::migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 6);
+		// Offers are written in their v6 layout, dated to the upgrade block, for `MigrateToV8`.
+		assert_eq!(v6_offer(BOB), (60, 10, 4));
+		assert_eq!(v6_offer(CHARLIE).2, 4);
+
+		// Running it again changes nothing.
+		System::set_block_number(5);
+		crate::migrations::v6::MigrateToV6::<Test>::on_runtime_upgrade();
+		assert_eq!(v6_offer(BOB).2, 4);
+	});
+}
+
+#[test]
+fn migrate_to_v7_works() {
+	use crate::CollectionSupply;
+	use frame_support::traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		for _ in 0..2 {
+			assert_ok!(KittiesModule::create_collection(
+				Origin::signed(ALICE),
+				None,
+				0,
+				MintPolicy::Public
+			));
+		}
+		assert_ok!(mint_into_collection(ALICE, 0, vec![1]));
+		assert_ok!(mint_into_collection(BOB, 0, vec![2]));
+		assert_ok!(mint_into_collection(BOB, 1, vec![3]));
+		assert_ok!(create_kitty(BOB, vec![4], 200, Permill::zero()));
+
+		// Forget the supply of every collection, as before v7.
+		StorageVersion::new(6).put::<KittiesModule>();
+		CollectionSupply::<Test>::remove(0);
+		CollectionSupply::<Test>::remove(1);
+
+		crate::migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 7);
+		assert_eq!(KittiesModule::collection_supply(0), 2);
+		assert_eq!(KittiesModule::collection_supply(1), 1);
+
+		// Running it again changes nothing.
+		crate::migrations::v7::MigrateToV7::<Test>::on_runtime_upgrade();
+		assert_eq!(KittiesModule::collection_supply(0), 2);
+	});
+}
+
+#[test]
+fn migrate_to_v8_works() {
+	use crate::NextMintNonce;
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(3);
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![2], 200, Permill::zero()));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![1], 60, 10));
+		assert_ok!(KittiesModule::make_offer(Origin::signed(CHARLIE), vec![2], 70, 10));
+
+		// Rewrite every kitty in its v5 layout, without a mint nonce, and every offer in its v6
+		// layout. The offer on kitty 2 predates it, so it was for an earlier kitty 2.
+		StorageVersion::new(7).put::<KittiesModule>();
+		NextMintNonce::<Test>::kill();
+		for (dna, kitty) in Kitties::<Test>::iter().collect::<Vec<_>>() {
+			let key = Blake2_128Concat::hash(&dna.encode());
+			let old_kitty = (
+				kitty.dna,
+				kitty.owner,
+				kitty.price,
+				kitty.gender,
+				kitty.parents,
+				kitty.generation,
+				kitty.creator,
+				kitty.royalty,
+				kitty.collection,
+				kitty.birth_block,
+				kitty.cooldown_until,
+			);
+			put_storage_value(b"KittiesModule", b"Kitties", &key, old_kitty);
+		}
+		for (dna, bidder, offer) in Offers::<Test>::iter().collect::<Vec<_>>() {
+			let mut key = Blake2_128Concat::hash(&dna.encode());
+			key.extend(Blake2_128Concat::hash(&bidder.encode()));
+			let made_at: u64 = if dna[..] == [1] { 3 } else { 2 };
+			let old_offer = (offer.amount, offer.expiry, made_at);
+			put_storage_value(b"KittiesModule", b"Offers", &key, old_offer);
+		}
+
+		crate::migrations::v8::MigrateToV8::<Test>::on_runtime_upgrade();
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 8);
+		assert_eq!(NextMintNonce::<Test>::get(), 2);
+		let nonce_of = |bytes: &[u8]| KittiesModule::kitties(dna(bytes)).unwrap().mint_nonce;
+		assert_ne!(nonce_of(&[1]), nonce_of(&[2]));
+		assert!(nonce_of(&[1]) < 2 && nonce_of(&[2]) < 2);
+		assert_eq!(KittiesModule::offers(dna(&[1]), CHARLIE).unwrap().kitty_nonce, nonce_of(&[1]));
+		assert_eq!(KittiesModule::offers(dna(&[2]), CHARLIE).unwrap().kitty_nonce, u64::MAX);
+		assert_eq!(KittiesModule::integrity_errors(), vec![]);
+
+		assert_ok!(KittiesModule::accept_offer(Origin::signed(ALICE), vec![1], CHARLIE));
+		assert_noop!(
+			KittiesModule::accept_offer(Origin::signed(BOB), vec![2], CHARLIE),
+			Error::<Test>::OfferOutdated
+		);
+
+		// Running it again changes nothing, and new kitties get fresh nonces.
+		crate::migrations::v8::MigrateToV8::<Test>::on_runtime_upgrade();
+		assert_eq!(NextMintNonce::<Test>::get(), 2);
+		assert_ok!(create_kitty(ALICE, vec![3], 100, Permill::zero()));
+		assert_eq!(nonce_of(&[3]), 2);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migrations_run_together_from_v0() {
+	use crate::migrations::*;
+	use codec::Encode;
+	use frame_support::{
+		storage::migration::put_storage_value,
+		traits::{GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+		Blake2_128Concat, StorageHasher,
+	};
+
+	// Every migration and the pallet's own hooks, run the way `Executive` runs them: all
+	// `pre_upgrade` hooks first, then every migration, then all `post_upgrade` hooks.
+	type Upgrade = (
+		v1::MigrateToV1<Test>,
+		v2::MigrateToV2<Test>,
+		v3::MigrateToV3<Test>,
+		v4::MigrateToV4<Test>,
+		v5::MigrateToV5<Test>,
+		v6::MigrateToV6<Test>,
+		v7::MigrateToV7<Test>,
+		v8::MigrateToV8<Test>,
+		KittiesModule,
+	);
+
+	// Kitty and owner index as stored before v1.
+	fn put_old_kitty(dna: &[u8], owner: u64) {
+		let key = Blake2_128Concat::hash(&dna.to_vec().encode());
+		let old_kitty = (dna.to_vec(), owner, 100u32, Gender::Male);
+		put_storage_value(b"KittiesModule", b"Kitties", &key, old_kitty);
+	}
+	fn put_old_owned(owner: u64, kitties: Vec<Vec<u8>>) {
+		let key = Blake2_128Concat::hash(&owner.encode());
+		put_storage_value(b"KittiesModule", b"KittiesOwned", &key, kitties);
+	}
+
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<KittiesModule>();
+		put_old_kitty(&[1, 2], ALICE);
+		put_old_kitty(&[3], ALICE);
+		put_old_kitty(&[4], BOB);
+		put_old_owned(ALICE, vec![vec![1, 2], vec![3]]);
+		put_old_owned(BOB, vec![vec![4]]);
+		crate::NumOfKitties::<Test>::put(3);
+
+		assert_ok!(Upgrade::pre_upgrade());
+		Upgrade::on_runtime_upgrade();
+		assert_ok!(Upgrade::post_upgrade());
+
+		assert_eq!(KittiesModule::on_chain_storage_version(), 8);
+		assert_eq!(KittiesModule::number_of_kitties(), 3);
+		let kitty = KittiesModule::kitties(dna(&[1, 2])).unwrap();
+		assert_eq!((kitty.owner, kitty.creator, kitty.collection), (ALICE, ALICE, None));
+		assert_eq!(KittiesModule::kitty_count_of(BOB), 1);
+	});
+
+	// A kitty dropped on the way fails the checks.
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(0).put::<KittiesModule>();
+		put_old_kitty(&[1], ALICE);
+		put_old_kitty(&[5; 33], ALICE);
+		put_old_owned(ALICE, vec![vec![1], vec![5; 33]]);
+		crate::NumOfKitties::<Test>::put(2);
+
+		assert_ok!(Upgrade::pre_upgrade());
+		Upgrade::on_runtime_upgrade();
+		assert!(Upgrade::post_upgrade().is_err());
+	});
+}
+
+#[test]
+fn integrity_holds_after_normal_use() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1, 2], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![3, 4, 5], 100, Permill::zero()));
+		assert_ok!(KittiesModule::breed_kitty(Origin::signed(ALICE), vec![1, 2], vec![3, 4, 5]));
+		assert_ok!(KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1, 2], BOB));
+		assert_ok!(KittiesModule::list_for_sale(Origin::signed(ALICE), vec![3, 4, 5]));
+		assert_ok!(KittiesModule::buy_kitty(Origin::signed(CHARLIE), vec![3, 4, 5], 100));
+		assert_ok!(KittiesModule::burn_kitty(Origin::signed(BOB), vec![1, 2]));
+
+		assert_eq!(KittiesModule::integrity_errors(), vec![]);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn integrity_errors_reports_every_inconsistency() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(TemplateModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		assert_ok!(create_kitty(ALICE, vec![2], 100, Permill::zero()));
+		assert_ok!(create_kitty(BOB, vec![3], 100, Permill::zero()));
+
+		crate::NumOfKitties::<Test>::put(5);
+		// Bob's index also lists Alice's kitty 1 and a kitty that does not exist, and loses 3,
+		// while his count stays at one.
+		KittiesOwned::<Test>::insert(BOB, dna(&[1]), ());
+		KittiesOwned::<Test>::insert(BOB, dna(&[9]), ());
+		KittiesOwned::<Test>::remove(BOB, dna(&[3]));
+
+		let errors = KittiesModule::integrity_errors();
+		assert_eq!(errors.len(), 6);
+		assert!(errors.contains(&IntegrityError::CountMismatch { recorded: 5, actual: 3 }));
+		assert!(errors.contains(&IntegrityError::MissingKitty { listed_under: BOB, dna: vec![9] }));
+		assert!(errors.contains(&IntegrityError::WrongOwner {
+			listed_under: BOB,
+			dna: vec![1],
+			owner: ALICE
+		}));
+		assert!(errors.contains(&IntegrityError::NotIndexed { dna: vec![3], owner: BOB }));
+		assert!(errors.contains(&IntegrityError::OwnerCountMismatch {
+			owner: BOB,
+			recorded: 1,
+			actual: 2
+		}));
+		let duplicate = errors
+			.iter()
+			.find_map(|error| match error {
+				IntegrityError::DuplicateDna { dna, listed_under } if *dna == vec![1] => {
+					Some(listed_under.clone())
+				},
+				_ => None,
+			})
+			.unwrap();
+		assert!(duplicate.contains(&ALICE) && duplicate.contains(&BOB));
+	});
+}
+
+#[test]
+fn transfer_fails_instead_of_panicking_when_index_drifts() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(create_kitty(ALICE, vec![1], 100, Permill::zero()));
+		KittiesOwned::<Test>::remove(ALICE, dna(&[1]));
+
+		assert_noop!(
+			KittiesModule::change_kitty_owner(Origin::signed(ALICE), vec![1], BOB),
+			Error::<Test>::KittyNotOwned
+		);
 	});
 }
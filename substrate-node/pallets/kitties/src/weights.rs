@@ -0,0 +1,874 @@
+//! Weights for pallet_kitties
+//!
+//! The figures below are estimates derived from each call's storage accesses and the shape of
+//! the benchmarks in `benchmarking.rs`, not measurements. Regenerate this whole file on
+//! reference hardware before deploying, from a node built with `--features runtime-benchmarks`:
+//!
+//! ./target/release/node-template benchmark pallet --chain=dev --steps=50 --repeat=20
+//!     --pallet=pallet_kitties --extrinsic=* --execution=wasm --wasm-execution=compiled
+//!     --template=./.maintain/frame-weight-template.hbs
+//!     --output=./pallets/kitties/src/weights.rs
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for pallet_kitties.
+pub trait WeightInfo {
+	fn create_kitty(d: u32, ) -> Weight;
+	fn change_kitty_owner() -> Weight;
+	fn set_price() -> Weight;
+	fn list_for_sale() -> Weight;
+	fn delist() -> Weight;
+	fn buy_kitty() -> Weight;
+	fn breed_kitty(d: u32, ) -> Weight;
+	fn mint_random_kitty() -> Weight;
+	fn burn_kitty(a: u32, ) -> Weight;
+	fn force_burn(a: u32, ) -> Weight;
+	fn approve() -> Weight;
+	fn set_approval_for_all() -> Weight;
+	fn transfer_from() -> Weight;
+	fn create_auction() -> Weight;
+	fn bid() -> Weight;
+	fn commit_kitty() -> Weight;
+	fn reveal_kitty(d: u32, ) -> Weight;
+	fn offer_transfer() -> Weight;
+	fn accept_transfer() -> Weight;
+	fn reject_transfer() -> Weight;
+	fn cancel_transfer() -> Weight;
+	fn make_offer() -> Weight;
+	fn accept_offer() -> Weight;
+	fn withdraw_offer() -> Weight;
+	fn lend_kitty() -> Weight;
+	fn accept_lease() -> Weight;
+	fn cancel_lease() -> Weight;
+	fn set_kitty_name() -> Weight;
+	fn set_metadata() -> Weight;
+	fn clear_metadata() -> Weight;
+	fn set_attribute() -> Weight;
+	fn clear_attribute() -> Weight;
+	fn create_collection() -> Weight;
+	fn destroy_collection() -> Weight;
+	fn set_collection_mint() -> Weight;
+	fn set_collection_admin() -> Weight;
+	fn set_allow_list() -> Weight;
+	fn freeze_collection_metadata() -> Weight;
+	fn mint_into_collection(d: u32, ) -> Weight;
+	fn lock_kitty() -> Weight;
+	fn unlock_kitty() -> Weight;
+	fn freeze_kitty() -> Weight;
+	fn thaw_kitty() -> Weight;
+	fn on_initialize(n: u32, ) -> Weight;
+	fn expire_commitments(n: u32, ) -> Weight;
+	fn expire_offers(n: u32, ) -> Weight;
+	fn expire_leases(n: u32, ) -> Weight;
+}
+
+/// Weights for pallet_kitties using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: KittiesModule Commitments (r:1 w:1)
+	// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule NextMintNonce (r:1 w:1)
+	fn create_kitty(d: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule KittiesOwned (r:1 w:2)
+	// Storage: KittiesModule KittiesOwnedCount (r:2 w:2)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn change_kitty_owner() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	fn set_price() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule PendingTransfers (r:1 w:0)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn list_for_sale() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule KittiesForSale (r:1 w:1)
+	fn delist() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesForSale (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: KittiesModule KittiesOwned (r:1 w:2)
+	// Storage: KittiesModule KittiesOwnedCount (r:2 w:2)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule PendingTransfers (r:0 w:1)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn buy_kitty() -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:3 w:3)
+	// Storage: KittiesModule Nonce (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule PendingTransfers (r:2 w:0)
+	// Storage: KittiesModule LockedKitties (r:2 w:0)
+	// Storage: KittiesModule FrozenKitties (r:2 w:0)
+	// Storage: KittiesModule KittyApprovals (r:2 w:0)
+	// Storage: KittiesModule OperatorApprovals (r:2 w:0)
+	// Storage: KittiesModule NextMintNonce (r:1 w:1)
+	// Storage: KittiesModule Leases (r:2 w:0)
+	fn breed_kitty(d: u32, ) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(21 as Weight))
+			.saturating_add(T::DbWeight::get().writes(10 as Weight))
+	}
+	// Storage: KittiesModule Nonce (r:1 w:1)
+	// Storage: RandomnessCollectiveFlip RandomMaterial (r:1 w:0)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule NextMintNonce (r:1 w:1)
+	fn mint_random_kitty() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule KittiesOwned (r:1 w:1)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule Leases (r:1 w:1)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule Metadata (r:1 w:1)
+	// Storage: KittiesModule Attributes (r:1 w:1)
+	// Storage: KittiesModule AttributeCount (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:1)
+	// Storage: KittiesModule FrozenKitties (r:1 w:1)
+	// Storage: KittiesModule CollectionSupply (r:1 w:1)
+	fn burn_kitty(a: u32, ) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(18 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:1 w:1)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:1 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: KittiesModule PendingTransfers (r:0 w:1)
+	// Storage: KittiesModule Leases (r:1 w:1)
+	// Storage: KittiesModule LeasesExpiring (r:1 w:1)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule Metadata (r:1 w:1)
+	// Storage: KittiesModule Attributes (r:1 w:1)
+	// Storage: KittiesModule AttributeCount (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:0 w:1)
+	// Storage: KittiesModule FrozenKitties (r:0 w:1)
+	// Storage: KittiesModule CollectionSupply (r:1 w:1)
+	fn force_burn(a: u32, ) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(T::DbWeight::get().writes(23 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	fn approve() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule OperatorApprovals (r:0 w:1)
+	fn set_approval_for_all() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittyApprovals (r:1 w:1)
+	// Storage: KittiesModule OperatorApprovals (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule KittiesOwned (r:1 w:2)
+	// Storage: KittiesModule KittiesOwnedCount (r:2 w:2)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn transfer_from() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule PendingTransfers (r:1 w:0)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn create_auction() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	fn bid() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Commitments (r:1 w:1)
+	// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn commit_kitty() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Commitments (r:1 w:1)
+	// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule NextMintNonce (r:1 w:1)
+	fn reveal_kitty(d: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn offer_transfer() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:1 w:2)
+	// Storage: KittiesModule KittiesOwnedCount (r:2 w:2)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn accept_transfer() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	fn reject_transfer() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	fn cancel_transfer() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Offers (r:1 w:1)
+	// Storage: KittiesModule OffersExpiring (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn make_offer() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule PendingTransfers (r:1 w:1)
+	// Storage: KittiesModule Offers (r:1 w:1)
+	// Storage: KittiesModule OffersExpiring (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:1 w:2)
+	// Storage: KittiesModule KittiesOwnedCount (r:2 w:2)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn accept_offer() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: KittiesModule Offers (r:1 w:1)
+	// Storage: KittiesModule OffersExpiring (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn withdraw_offer() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule PendingTransfers (r:1 w:0)
+	// Storage: KittiesModule Leases (r:1 w:0)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn lend_kitty() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule LeaseOffers (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule PendingTransfers (r:1 w:0)
+	// Storage: KittiesModule Leases (r:1 w:1)
+	// Storage: KittiesModule LeasesExpiring (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn accept_lease() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule LeaseOffers (r:1 w:1)
+	fn cancel_lease() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Collections (r:1 w:0)
+	fn set_kitty_name() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Collections (r:1 w:0)
+	fn set_metadata() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Metadata (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Collections (r:1 w:0)
+	fn clear_metadata() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Attributes (r:1 w:1)
+	// Storage: KittiesModule AttributeCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Collections (r:1 w:0)
+	fn set_attribute() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Attributes (r:1 w:1)
+	// Storage: KittiesModule AttributeCount (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule Collections (r:1 w:0)
+	fn clear_attribute() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: KittiesModule NextCollectionId (r:1 w:1)
+	// Storage: KittiesModule Collections (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule CollectionDeposits (r:0 w:1)
+	fn create_collection() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: KittiesModule Collections (r:1 w:1)
+	// Storage: KittiesModule CollectionSupply (r:1 w:1)
+	// Storage: KittiesModule CollectionDeposits (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn destroy_collection() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: KittiesModule Collections (r:1 w:1)
+	// Storage: KittiesModule CollectionAdmins (r:1 w:0)
+	fn set_collection_mint() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Collections (r:1 w:0)
+	// Storage: KittiesModule CollectionAdmins (r:0 w:1)
+	fn set_collection_admin() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Collections (r:1 w:0)
+	// Storage: KittiesModule CollectionAdmins (r:1 w:0)
+	// Storage: KittiesModule CollectionAllowList (r:0 w:1)
+	fn set_allow_list() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Collections (r:1 w:1)
+	fn freeze_collection_metadata() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Collections (r:1 w:1)
+	// Storage: KittiesModule CollectionAdmins (r:1 w:0)
+	// Storage: KittiesModule CollectionAllowList (r:1 w:0)
+	// Storage: KittiesModule Commitments (r:1 w:1)
+	// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesOwnedCount (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:0 w:1)
+	// Storage: KittiesModule NumOfKitties (r:1 w:1)
+	// Storage: KittiesModule KittyDeposits (r:0 w:1)
+	// Storage: KittiesModule CollectionSupply (r:1 w:1)
+	// Storage: KittiesModule NextMintNonce (r:1 w:1)
+	fn mint_into_collection(d: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:0)
+	// Storage: KittiesModule LockedKitties (r:0 w:1)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	fn lock_kitty() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule LockedKitties (r:1 w:1)
+	fn unlock_kitty() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule Kitties (r:1 w:0)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: KittiesModule FrozenKitties (r:0 w:1)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	fn freeze_kitty() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: KittiesModule FrozenKitties (r:1 w:1)
+	fn thaw_kitty() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: KittiesModule AuctionsEnding (r:1 w:1)
+	// Storage: KittiesModule Auctions (r:1 w:1)
+	// Storage: KittiesModule Kitties (r:1 w:1)
+	// Storage: KittiesModule KittiesOwned (r:1 w:2)
+	// Storage: KittiesModule KittiesOwnedCount (r:2 w:2)
+	// Storage: KittiesModule KittiesForSale (r:0 w:1)
+	// Storage: KittiesModule KittyApprovals (r:0 w:1)
+	// Storage: System Account (r:3 w:3)
+	// Storage: KittiesModule PendingTransfers (r:0 w:1)
+	// Storage: KittiesModule LeaseOffers (r:0 w:1)
+	// Storage: KittiesModule LockedKitties (r:1 w:0)
+	// Storage: KittiesModule FrozenKitties (r:1 w:0)
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((53_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((13 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: KittiesModule CommitmentsExpiring (r:1 w:1)
+	// Storage: KittiesModule Commitments (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn expire_commitments(n: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((22_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: KittiesModule OfferExpiryCursor (r:1 w:1)
+	// Storage: KittiesModule OffersExpiring (r:1 w:1)
+	// Storage: KittiesModule Offers (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn expire_offers(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	// Storage: KittiesModule LeasesExpiring (r:1 w:1)
+	// Storage: KittiesModule Leases (r:1 w:1)
+	fn expire_leases(n: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn create_kitty(d: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn change_kitty_owner() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn set_price() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn list_for_sale() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn delist() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn buy_kitty() -> Weight {
+		(49_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn breed_kitty(d: u32, ) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((5_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(21 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(10 as Weight))
+	}
+	fn mint_random_kitty() -> Weight {
+		(28_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn burn_kitty(a: u32, ) -> Weight {
+		(36_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(18 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	fn force_burn(a: u32, ) -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add((6_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(15 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(a as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(23 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(a as Weight)))
+	}
+	fn approve() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_approval_for_all() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn transfer_from() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn create_auction() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn bid() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn commit_kitty() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn reveal_kitty(d: u32, ) -> Weight {
+		(40_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn offer_transfer() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn accept_transfer() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn reject_transfer() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn cancel_transfer() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn make_offer() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn accept_offer() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	fn withdraw_offer() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn lend_kitty() -> Weight {
+		(24_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accept_lease() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn cancel_lease() -> Weight {
+		(19_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_kitty_name() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_metadata() -> Weight {
+		(22_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn clear_metadata() -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_attribute() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn clear_attribute() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn create_collection() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn destroy_collection() -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn set_collection_mint() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_collection_admin() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_allow_list() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn freeze_collection_metadata() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn mint_into_collection(d: u32, ) -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add((3_000 as Weight).saturating_mul(d as Weight))
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn lock_kitty() -> Weight {
+		(18_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn unlock_kitty() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn freeze_kitty() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn thaw_kitty() -> Weight {
+		(14_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn on_initialize(n: u32, ) -> Weight {
+		(3_000_000 as Weight)
+			.saturating_add((53_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((10 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((13 as Weight).saturating_mul(n as Weight)))
+	}
+	fn expire_commitments(n: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((22_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn expire_offers(n: u32, ) -> Weight {
+		(4_000_000 as Weight)
+			.saturating_add((21_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
+	fn expire_leases(n: u32, ) -> Weight {
+		(2_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+}
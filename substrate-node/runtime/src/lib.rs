@@ -27,7 +27,8 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness, StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
+		StorageInfo,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
@@ -102,12 +103,13 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	// The version of the runtime specification. A full node will not attempt to use its native
 	//   runtime in substitute for the on-chain Wasm runtime unless all of `spec_name`,
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
-	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
-	//   the compatible custom types.
-	spec_version: 100,
+	// This value started at 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
+	//   the compatible custom types, and is bumped with every runtime upgrade.
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	// Bumped whenever the calls or their encoding change, which invalidates signed transactions.
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -269,9 +271,40 @@ impl pallet_template::Config for Runtime {
 	type Event = Event;
 }
 
+parameter_types! {
+	pub const MaxRoyalty: Permill = Permill::from_percent(10);
+	pub KittyCooldowns: Vec<BlockNumber> =
+		vec![MINUTES, 5 * MINUTES, 30 * MINUTES, 2 * HOURS, 8 * HOURS, DAYS, 7 * DAYS];
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_kitties::Config for Runtime {
 	type Event = Event;
+	type Currency = Balances;
+	type KittyRandomness = RandomnessCollectiveFlip;
+	type GenomeDecoder = pallet_kitties::ByteGenomeDecoder;
+	type MaxDnaLen = ConstU32<64>;
+	type MaxKittiesOwned = ConstU32<100>;
+	type KittyDeposit = ConstU128<10_000_000_000>;
+	type MaxAuctionsPerBlock = ConstU32<50>;
+	type CommitDeposit = ConstU128<1_000_000_000>;
+	type CommitPeriod = ConstU32<{ 10 * MINUTES }>;
+	type MaxCommitsPerBlock = ConstU32<50>;
+	type AllowDirectTransfer = ConstBool<true>;
+	type MaxOffersPerBlock = ConstU32<50>;
+	type MaxRoyalty = MaxRoyalty;
+	type MaxLeasesPerBlock = ConstU32<50>;
+	type MaxNameLen = ConstU32<64>;
+	type MaxUriLen = ConstU32<256>;
+	type MaxAttributeKeyLen = ConstU32<32>;
+	type MaxAttributeValueLen = ConstU32<64>;
+	type MaxAttributes = ConstU32<16>;
+	type MetadataDepositBase = ConstU128<1_000_000_000>;
+	type AttributeDepositBase = ConstU128<500_000_000>;
+	type DepositPerByte = ConstU128<10_000_000>;
+	type CollectionDeposit = ConstU128<100_000_000_000>;
+	type CooldownSchedule = KittyCooldowns;
+	type WeightInfo = pallet_kitties::weights::SubstrateWeight<Runtime>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -323,8 +356,21 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+/// Storage migrations applied on runtime upgrade, oldest first.
+pub type Migrations = (
+	pallet_kitties::migrations::v1::MigrateToV1<Runtime>,
+	pallet_kitties::migrations::v2::MigrateToV2<Runtime>,
+	pallet_kitties::migrations::v3::MigrateToV3<Runtime>,
+	pallet_kitties::migrations::v4::MigrateToV4<Runtime>,
+	pallet_kitties::migrations::v5::MigrateToV5<Runtime>,
+	pallet_kitties::migrations::v6::MigrateToV6<Runtime>,
+	pallet_kitties::migrations::v7::MigrateToV7<Runtime>,
+	pallet_kitties::migrations::v8::MigrateToV8<Runtime>,
+);
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -457,6 +503,48 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_rpc_runtime_api::KittiesApi<
+		Block,
+		AccountId,
+		Balance,
+		pallet_kitties::Kitty<Runtime>,
+		pallet_kitties::Genome,
+		pallet_kitties::MetadataInfo,
+		pallet_kitties::IntegrityError<AccountId>,
+	> for Runtime {
+		fn kitty(dna: Vec<u8>) -> Option<pallet_kitties::Kitty<Runtime>> {
+			KittiesModule::kitty_by_dna(dna)
+		}
+
+		fn genome(dna: Vec<u8>) -> Option<pallet_kitties::Genome> {
+			KittiesModule::genome(dna)
+		}
+
+		fn kitties_of(owner: AccountId, start_after: Option<Vec<u8>>, limit: u32) -> Vec<Vec<u8>> {
+			KittiesModule::kitties_of(owner, start_after, limit)
+		}
+
+		fn kitty_count() -> u32 {
+			KittiesModule::number_of_kitties()
+		}
+
+		fn kitty_user(dna: Vec<u8>) -> Option<AccountId> {
+			KittiesModule::kitty_user(dna)
+		}
+
+		fn metadata(dna: Vec<u8>) -> Option<pallet_kitties::MetadataInfo> {
+			KittiesModule::metadata(dna)
+		}
+
+		fn listed_kitties() -> Vec<(Vec<u8>, Balance)> {
+			KittiesModule::listed_kitties()
+		}
+
+		fn integrity_errors() -> Vec<pallet_kitties::IntegrityError<AccountId>> {
+			KittiesModule::integrity_errors()
+		}
+	}
+
 	impl pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi<Block, Balance> for Runtime {
 		fn query_info(
 			uxt: <Block as BlockT>::Extrinsic,